@@ -0,0 +1,53 @@
+//! Generates the solution registry when the `registry` feature is enabled.
+//!
+//! Every `src/bin/YYYY_NN.rs` is compiled into the library as a module, and its `SOLUTION`
+//! (created by `solution!`) is collected into `SOLUTIONS` with its year.
+use std::{env, fs, path::Path};
+
+fn main() {
+    if env::var_os("CARGO_FEATURE_REGISTRY").is_none() {
+        // don't rebuild the library when solutions change if the registry is not used.
+        println!("cargo:rerun-if-changed=build.rs");
+        return;
+    }
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    // e.g. `2024_01`.
+    let mut puzzles: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "rs" {
+                return None;
+            }
+            let (year, day) = path.file_stem()?.to_str()?.split_once('_')?;
+            let is_puzzle = year.len() == 4
+                && day.len() == 2
+                && year.bytes().chain(day.bytes()).all(|b| b.is_ascii_digit());
+            is_puzzle.then(|| (year.to_string(), day.to_string()))
+        })
+        .collect();
+    puzzles.sort_unstable();
+
+    let mut out = String::new();
+
+    for (year, day) in &puzzles {
+        let path = bin_dir.join(format!("{year}_{day}.rs"));
+        out.push_str(&format!(
+            "#[path = {:?}]\n#[allow(dead_code)]\nmod day_{year}_{day};\n",
+            path.display().to_string()
+        ));
+    }
+
+    out.push_str("\npub const SOLUTIONS: &[(u16, super::Solution)] = &[\n");
+    for (year, day) in &puzzles {
+        out.push_str(&format!("    ({year}, day_{year}_{day}::SOLUTION),\n"));
+    }
+    out.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+}
@@ -78,6 +78,19 @@ fn read_all_input(input: &str) -> IResult<&str, (Vec<Rule>, Pages)> {
     Ok((input, (rules, pages)))
 }
 
+/// Parses the input, reporting the first line that could not be parsed.
+fn parse(input: &str) -> Result<(Vec<Rule>, Pages), String> {
+    read_all_input(input)
+        .map(|(_, parsed)| parsed)
+        .map_err(|e| {
+            let line = match &e {
+                nom::Err::Error(e) | nom::Err::Failure(e) => e.input.lines().next(),
+                nom::Err::Incomplete(_) => None,
+            };
+            format!("could not parse {:?}", line.unwrap_or_default())
+        })
+}
+
 fn fix_page_order_according_to_rules(
     rules: &[Rule],
     pages: &mut HashMap<usize, usize>,
@@ -103,25 +116,25 @@ fn fix_page_order_according_to_rules(
     pages
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (_, (rules, all_pages)) = read_all_input(input).unwrap();
+pub fn part_one(input: &str) -> Result<u32, String> {
+    let (rules, all_pages) = parse(input)?;
     let result: usize = all_pages
         .iter()
         .filter(|pages| rules.iter().all(|rule| rule_applies(rule, pages)))
         .map(middle_page)
         .sum();
-    Some(result as u32)
+    Ok(result as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (_, (rules, all_pages)) = read_all_input(input).unwrap();
+pub fn part_two(input: &str) -> Result<u32, String> {
+    let (rules, all_pages) = parse(input)?;
     let result: usize = all_pages
         .iter()
         .filter(|pages| !rules.iter().all(|rule| rule_applies(rule, pages)))
         .map(|pages| fix_page_order_according_to_rules(&rules, &mut pages.clone()))
         .map(|pages| middle_page(&pages))
         .sum();
-    Some(result as u32)
+    Ok(result as u32)
 }
 
 #[cfg(test)]
@@ -131,13 +144,13 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
+        assert_eq!(result, Ok(143));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
+        assert_eq!(result, Ok(123));
     }
 
     #[test]
@@ -1,4 +1,10 @@
-advent_of_code::solution!(9);
+advent_of_code::solution!(
+    9,
+    part_one = [part_one, part_one_blocks],
+    part_two = [part_two]
+);
+
+use std::collections::VecDeque;
 
 use itertools::Itertools;
 
@@ -90,6 +96,35 @@ fn compress_blocks(blocks: &[Block]) -> Vec<Block> {
     blocks
 }
 
+fn fragment_blocks(blocks: &[Block]) -> Vec<Block> {
+    // Fill the spaces from the left with the files from the right
+    // Splitting a file if the space is too small for it
+    let mut blocks: VecDeque<Block> = blocks.iter().copied().collect();
+    let mut compressed = Vec::new();
+    while let Some(block) = blocks.pop_front() {
+        let Block::Space(mut space_length) = block else {
+            compressed.push(block);
+            continue;
+        };
+        while space_length > 0 {
+            match blocks.pop_back() {
+                Some(Block::File(id, length)) => {
+                    let moved = length.min(space_length);
+                    compressed.push(Block::File(id, moved));
+                    space_length -= moved;
+                    if moved < length {
+                        blocks.push_back(Block::File(id, length - moved));
+                    }
+                }
+                // Trailing spaces stay at the end
+                Some(Block::Space(_)) => {}
+                None => break,
+            }
+        }
+    }
+    compressed
+}
+
 fn input_to_file_system(input: &str) -> FileSystem {
     // The input will be one line of pairs of single digits
     // Of each pair, the first digit will be file length
@@ -167,6 +202,13 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(checksum)
 }
 
+pub fn part_one_blocks(input: &str) -> Option<usize> {
+    let blocks = input_to_blocks(input);
+    let compressed = fragment_blocks(&blocks);
+    let checksum = checksum_for_blocks(&compressed);
+    Some(checksum)
+}
+
 pub fn part_two(input: &str) -> Option<usize> {
     let blocks = input_to_blocks(input);
     let compressed = compress_blocks(&blocks);
@@ -184,6 +226,12 @@ mod tests {
         assert_eq!(result, Some(1928));
     }
 
+    #[test]
+    fn test_part_one_blocks() {
+        let result = part_one_blocks(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(1928));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
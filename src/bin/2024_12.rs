@@ -1,4 +1,4 @@
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, parse = parse);
 
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -296,24 +296,30 @@ fn score_with_sides(map: &HashMap<Coordinate, Plot>, sides: HashMap<usize, usize
     // now we have fields sizes and sides, both indexed by id so we can calculate the score
     field_sizes
         .iter()
-        .map(|(id, size)| (size * sides.get(id).unwrap()))
+        .map(|(id, size)| size * sides.get(id).unwrap())
         .sum()
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (mut map, max) = input_to_map(input);
-    group_plots(&mut map, max);
-    mark_fences(&mut map, max);
-    Some(score(&map))
+pub struct Garden {
+    map: HashMap<Coordinate, Plot>,
+    max: Coordinate,
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn parse(input: &str) -> Garden {
     let (mut map, max) = input_to_map(input);
     group_plots(&mut map, max);
     mark_fences(&mut map, max);
-    let sides = find_all_sides(&map, max);
+    Garden { map, max }
+}
+
+pub fn part_one(garden: &Garden) -> Option<usize> {
+    Some(score(&garden.map))
+}
+
+pub fn part_two(garden: &Garden) -> Option<usize> {
+    let sides = find_all_sides(&garden.map, garden.max);
 
-    Some(score_with_sides(&map, sides))
+    Some(score_with_sides(&garden.map, sides))
 }
 
 #[cfg(test)]
@@ -322,13 +328,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(1930));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(1206));
     }
 
@@ -1,5 +1,6 @@
-advent_of_code::solution!(14);
+advent_of_code::solution!(14, params = Params);
 
+use advent_of_code::template::PuzzleParams;
 use nalgebra::Vector2;
 use scan_fmt::*;
 
@@ -7,6 +8,29 @@ type Vec2 = Vector2<i64>;
 
 use std::collections::HashSet;
 
+pub struct Params {
+    /// The width and height of the space the robots move in.
+    max: Vec2,
+    /// The seconds the robots move in part one.
+    seconds: usize,
+}
+
+impl PuzzleParams for Params {
+    fn real() -> Self {
+        Params {
+            max: Vec2::new(101, 103),
+            seconds: 100,
+        }
+    }
+
+    fn example() -> Self {
+        Params {
+            max: Vec2::new(11, 7),
+            seconds: 100,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Robot {
     position: Vec2,
@@ -119,10 +143,10 @@ fn find_tree(robots: &mut [Robot], max: Vec2) -> usize {
     0
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
+pub fn part_one(input: &str, params: &Params) -> Option<usize> {
     let mut robots = parse_input(input);
-    let max = Vec2::new(101, 103);
-    for _ in 0..100 {
+    let max = params.max;
+    for _ in 0..params.seconds {
         for robot in robots.iter_mut() {
             move_robot(robot, max);
         }
@@ -130,9 +154,9 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(calculate_safety_factor(&robots, max))
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn part_two(input: &str, params: &Params) -> Option<usize> {
     let mut robots = parse_input(input);
-    let max = Vec2::new(101, 103);
+    let max = params.max;
     let time = find_tree(&mut robots, max);
     Some(time)
 }
@@ -144,15 +168,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = &advent_of_code::template::read_file("examples", DAY);
-        let mut robots = parse_input(input);
-        println!("--------------");
-        let max = Vec2::new(11, 7);
-        for _ in 0..100 {
-            for robot in robots.iter_mut() {
-                move_robot(robot, max);
-            }
-        }
-        let result = Some(calculate_safety_factor(&robots, max));
+        let result = part_one(input, &Params::example());
         assert_eq!(result, Some(12));
     }
 
@@ -121,7 +121,7 @@ fn solve(map: &Map, start: (usize, usize), end: (usize, usize)) -> Option<usize>
         &start_node,
         |node| successors(node, map),
         |_| 0,
-        |node| (node.x == end.0 && node.y == end.1),
+        |node| node.x == end.0 && node.y == end.1,
     )?;
     Some(result.1)
 }
@@ -141,7 +141,7 @@ fn find_all_places_on_best_path(
         &start_node,
         |node| successors(node, map),
         |_| 0,
-        |node| (node.x == end.0 && node.y == end.1),
+        |node| node.x == end.0 && node.y == end.1,
     )
     .expect("Can't find any path");
     Some(
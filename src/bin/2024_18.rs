@@ -1,4 +1,5 @@
-advent_of_code::solution!(18);
+advent_of_code::solution!(18, params = Params);
+use advent_of_code::template::PuzzleParams;
 use pathfinding::prelude::*;
 use scan_fmt::scan_fmt;
 use std::collections::HashSet;
@@ -16,6 +17,29 @@ struct Node {
 
 type Obstacles = HashSet<Position>;
 
+pub struct Params {
+    /// The bottom right corner of the memory space.
+    max: Position,
+    /// The number of bytes that have fallen in part one.
+    bytes: usize,
+}
+
+impl PuzzleParams for Params {
+    fn real() -> Self {
+        Params {
+            max: Position { x: 70, y: 70 },
+            bytes: 1024,
+        }
+    }
+
+    fn example() -> Self {
+        Params {
+            max: Position { x: 6, y: 6 },
+            bytes: 12,
+        }
+    }
+}
+
 fn successors(node: &Node, obstacles: &Obstacles, max: &Position) -> Vec<(Node, usize)> {
     let mut result = Vec::new();
     let x = node.position.x;
@@ -95,13 +119,17 @@ fn binary_search(all_obstacles: &[Position], max: &Position, start: usize) -> Po
     all_obstacles[low - 1].clone()
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
+pub fn part_one(input: &str, params: &Params) -> Option<usize> {
     let obstacles = parse_obstacles(input);
-    let obstacles = obstacles.iter().take(1024).cloned().collect::<Obstacles>();
+    let obstacles = obstacles
+        .iter()
+        .take(params.bytes)
+        .cloned()
+        .collect::<Obstacles>();
     let start = Node {
         position: Position { x: 0, y: 0 },
     };
-    let max = Position { x: 70, y: 70 };
+    let max = params.max.clone();
     let end = Node {
         position: max.clone(),
     };
@@ -114,10 +142,28 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(result?.1)
 }
 
-pub fn part_two(input: &str) -> Option<String> {
+pub fn part_two(input: &str, params: &Params) -> Option<String> {
     let obstacles = parse_obstacles(input);
-    let start = 1024;
-    let position = binary_search(&obstacles, &Position { x: 70, y: 70 }, start);
+    let position = binary_search(&obstacles, &params.max, params.bytes);
     let result = format!("{},{}", position.x, position.y);
     Some(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = &advent_of_code::template::read_file("examples", DAY);
+        let result = part_one(input, &Params::example());
+        assert_eq!(result, Some(22));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = &advent_of_code::template::read_file("examples", DAY);
+        let result = part_two(input, &Params::example());
+        assert_eq!(result, Some("6,1".to_string()));
+    }
+}
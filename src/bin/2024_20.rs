@@ -1,8 +1,24 @@
-advent_of_code::solution!(20);
+advent_of_code::solution!(20, params = Params);
 
+use advent_of_code::template::PuzzleParams;
 use pathfinding::prelude::astar;
 use std::collections::{HashMap, HashSet};
 
+pub struct Params {
+    /// The picoseconds a cheat has to save at least to be counted, per part.
+    target: [usize; 2],
+}
+
+impl PuzzleParams for Params {
+    fn real() -> Self {
+        Params { target: [100, 100] }
+    }
+
+    fn example() -> Self {
+        Params { target: [20, 50] }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Position {
     x: usize,
@@ -193,7 +209,7 @@ fn try_all_cheats_along_path(
     result
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
+pub fn part_one(input: &str, params: &Params) -> Option<usize> {
     let (map, start, end) = input_to_map(input);
     let start = Node { position: start };
     let path_without_cheating = find_path(&map, start, &end).unwrap();
@@ -204,13 +220,13 @@ pub fn part_one(input: &str) -> Option<usize> {
             y: acc.y.max(pos.y),
         });
     let cheat_path_savings = try_all_cheats_along_path(&map, path_without_cheating, &end, &max, 2);
-    let target = 100;
+    let target = params.target[0];
     let result = cheat_path_savings.iter().filter(|x| **x >= target).count();
 
     Some(result)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn part_two(input: &str, params: &Params) -> Option<usize> {
     let (map, start, end) = input_to_map(input);
     let start = Node { position: start };
     let path_without_cheating = find_path(&map, start, &end).unwrap();
@@ -221,7 +237,7 @@ pub fn part_two(input: &str) -> Option<usize> {
             y: acc.y.max(pos.y),
         });
     let cheat_path_savings = try_all_cheats_along_path(&map, path_without_cheating, &end, &max, 20);
-    let target = 100;
+    let target = params.target[1];
     let result = cheat_path_savings.iter().filter(|x| **x >= target).count();
 
     Some(result)
@@ -274,6 +290,20 @@ mod tests {
         assert_eq!(all_cheat_paths.len(), 44);
     }
 
+    #[test]
+    fn test_part_one_with_example_params() {
+        let input = &advent_of_code::template::read_file("examples", DAY);
+        let result = part_one(input, &Params::example());
+        assert_eq!(result, Some(5));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = &advent_of_code::template::read_file("examples", DAY);
+        let result = part_two(input, &Params::example());
+        assert_eq!(result, Some(285));
+    }
+
     #[test]
     fn test_locations_n_away() {
         let input = &advent_of_code::template::read_file("examples", DAY);
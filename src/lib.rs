@@ -1,3 +1,10 @@
+// Lets `solution!` refer to this crate by name when solutions are compiled into the registry.
+extern crate self as advent_of_code;
+
 pub mod template;
 
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
 // Use this file to add helper functions and additional modules.
@@ -1,89 +1,223 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, lint_solutions, read, report, scaffold, solve, time, verify, watch,
+};
+use advent_of_code::template::{config, Error, PuzzleId};
 use args::{parse, AppArguments};
+use std::process;
 
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::runner::InputSource;
+    use advent_of_code::template::{Day, Execution, Limits, StatColumn, Year};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    /// Slowdown in percent that `time --compare` tolerates by default.
+    const DEFAULT_REGRESSION_THRESHOLD: f64 = 10.0;
 
     pub enum AppArguments {
         Download {
+            year: Year,
             day: Day,
         },
         Read {
+            year: Year,
             day: Day,
         },
         Scaffold {
+            year: Year,
             day: Day,
             download: bool,
             overwrite: bool,
+            from_puzzle: bool,
+            template: Option<String>,
         },
         Solve {
+            year: Year,
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            in_process: bool,
+            variants: bool,
+            input: InputSource,
         },
         All {
+            year: Year,
             release: bool,
+            execution: Execution,
+            limits: Limits,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            stats: Vec<StatColumn>,
+            compare: Option<f64>,
+            memory: bool,
+            execution: Execution,
+            limits: Limits,
+        },
+        TimeHistory {
+            year: Year,
+            day: Day,
+        },
+        Report {
+            year: Year,
+            out: Option<PathBuf>,
+        },
+        Watch {
+            year: Year,
+            day: Day,
+            release: bool,
+        },
+        Verify {
+            year: Year,
+            day: Option<Day>,
+            execution: Execution,
+            limits: Limits,
+            record: bool,
+        },
+        LintSolutions {
+            year: Year,
+            day: Option<Day>,
+            limits: Limits,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            year: Year,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        parse_args(pico_args::Arguments::from_env())
+    }
 
-        let app_args = match args.subcommand()?.as_deref() {
+    pub fn parse_args(
+        mut args: pico_args::Arguments,
+    ) -> Result<AppArguments, Box<dyn std::error::Error>> {
+        let subcommand = args.subcommand()?;
+        // every subcommand accepts `--year`, defaulting to `AOC_YEAR`.
+        let year = args
+            .opt_value_from_str("--year")?
+            .unwrap_or_else(Year::current);
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year,
                 release: args.contains("--release"),
+                execution: if args.contains("--in-process") {
+                    Execution::InProcess
+                } else {
+                    Execution::from_jobs(args.opt_value_from_str("--jobs")?)
+                },
+                limits: parse_limits(&mut args)?,
+            },
+            Some("time") if args.contains("--history") => AppArguments::TimeHistory {
+                year,
+                day: parse_day(&mut args, year)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let memory = args.contains("--memory");
+                let stats = args
+                    .opt_value_from_fn("--stats", parse_stat_columns)?
+                    .unwrap_or_default();
+                let threshold = args.opt_value_from_str("--threshold")?;
+                let compare = args
+                    .contains("--compare")
+                    .then(|| threshold.unwrap_or(DEFAULT_REGRESSION_THRESHOLD));
+                let jobs: Option<usize> = args.opt_value_from_str("--jobs")?;
+                // options with values go first, otherwise the day would take their value.
+                let limits = parse_limits(&mut args)?;
+                // `--jobs` builds all solutions up front, but concurrent benches compete for
+                // the CPU. They only run side by side with `--parallel-bench`.
+                let execution = if args.contains("--in-process") {
+                    Execution::InProcess
+                } else if args.contains("--parallel-bench") {
+                    if jobs.is_none() {
+                        return Err("`--parallel-bench` requires `--jobs <n>`.".into());
+                    }
+                    Execution::from_jobs(jobs)
+                } else {
+                    jobs.map_or(Execution::Serial, |_| Execution::Parallel { jobs: 1 })
+                };
 
                 AppArguments::Time {
+                    year,
                     all,
-                    day: args.opt_free_from_str()?,
+                    day: parse_opt_day(&mut args, year)?,
                     store,
+                    stats,
+                    compare,
+                    memory,
+                    execution,
+                    limits,
                 }
             }
+            Some("report") => AppArguments::Report {
+                year,
+                out: args.opt_value_from_str("--out")?,
+            },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                year,
+                day: parse_day(&mut args, year)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                year,
+                day: parse_day(&mut args, year)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                year,
+                day: parse_day(&mut args, year)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                from_puzzle: args.contains("--from-puzzle"),
+                template: args.opt_value_from_str("--template")?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                year,
+                day: parse_day(&mut args, year)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                in_process: args.contains("--in-process"),
+                variants: args.contains("--variants"),
+                input: InputSource::from_flags(
+                    args.opt_value_from_str("--input")?,
+                    args.opt_value_from_str("--example-file")?,
+                    args.contains("--example"),
+                )?,
+            },
+            Some("watch") => AppArguments::Watch {
+                year,
+                day: parse_day(&mut args, year)?,
+                release: args.contains("--release"),
+            },
+            Some("verify") => AppArguments::Verify {
+                year,
+                execution: if args.contains("--in-process") {
+                    Execution::InProcess
+                } else {
+                    Execution::from_jobs(args.opt_value_from_str("--jobs")?)
+                },
+                limits: parse_limits(&mut args)?,
+                record: args.contains("--record"),
+                day: parse_opt_day(&mut args, year)?,
+            },
+            Some("lint-solutions") => AppArguments::LintSolutions {
+                year,
+                limits: parse_limits(&mut args)?,
+                day: parse_opt_day(&mut args, year)?,
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            Some("today") => AppArguments::Today { year },
+            Some(x) => return Err(format!("Unknown command: {x}").into()),
+            None => return Err("No command specified.".into()),
         };
 
         let remaining = args.finish();
@@ -93,52 +227,212 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses the day argument, which has to be a puzzle day of `year`.
+    fn parse_day(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Day, Box<dyn std::error::Error>> {
+        parse_opt_day(args, year)?.ok_or_else(|| pico_args::Error::MissingArgument.into())
+    }
+
+    fn parse_opt_day(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Option<Day>, Box<dyn std::error::Error>> {
+        let day: Option<String> = args.opt_free_from_str()?;
+        Ok(day.map(|day| Day::parse_in_year(&day, year)).transpose()?)
+    }
+
+    /// Parses `--timeout` and `--part-timeout` (seconds), `--max-memory` (MiB) and `--max-cpu` (seconds).
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, Box<dyn std::error::Error>> {
+        let memory_mib: Option<u64> = args.opt_value_from_str("--max-memory")?;
+
+        Ok(Limits {
+            day_timeout: args.opt_value_from_fn("--timeout", parse_seconds)?,
+            part_timeout: args.opt_value_from_fn("--part-timeout", parse_seconds)?,
+            memory_bytes: memory_mib.map(|mib| mib * 1024 * 1024),
+            cpu_seconds: args.opt_value_from_str("--max-cpu")?,
+        })
+    }
+
+    fn parse_seconds(s: &str) -> Result<Duration, Box<dyn std::error::Error>> {
+        Ok(Duration::try_from_secs_f64(s.parse()?)?)
+    }
+
+    /// Parses a comma-separated list of statistics columns, or `all`.
+    fn parse_stat_columns(s: &str) -> Result<Vec<StatColumn>, Box<dyn std::error::Error>> {
+        if s == "all" {
+            return Ok(StatColumn::ALL.to_vec());
+        }
+        Ok(s.split(',').map(str::parse).collect::<Result<_, _>>()?)
+    }
 }
 
 fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
-        }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
-            }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+    // the arguments default to settings of the config, e.g. the year.
+    let result = config::load()
+        .and_then(|_| parse().map_err(|err| Error::Usage(err.to_string())))
+        .and_then(run);
+
+    if let Err(err) = result {
+        eprintln!("Error: {err}");
+        process::exit(err.exit_code());
+    }
+}
+
+fn run(args: AppArguments) -> Result<(), Error> {
+    match args {
+        AppArguments::All {
+            year,
+            release,
+            execution,
+            limits,
+        } => all::handle(year, release, execution, limits),
+        AppArguments::Time {
+            year,
+            day,
+            all,
+            store,
+            stats,
+            compare,
+            memory,
+            execution,
+            limits,
+        } => time::handle(
+            year, day, all, store, &stats, compare, memory, execution, limits,
+        ),
+        AppArguments::TimeHistory { year, day } => time::handle_history(year, day),
+        AppArguments::Report { year, out } => report::handle(year, out),
+        AppArguments::Download { year, day } => download::handle(PuzzleId::new(year, day)),
+        AppArguments::Read { year, day } => read::handle(PuzzleId::new(year, day)),
+        AppArguments::Scaffold {
+            year,
+            day,
+            download,
+            overwrite,
+            from_puzzle,
+            template,
+        } => {
+            let puzzle = PuzzleId::new(year, day);
+            let template = template.unwrap_or_else(|| config::get().scaffold.template.clone());
+            // download first, so that the template can use the title of the puzzle.
+            let downloaded = if download {
+                download::handle(puzzle)
+            } else {
+                Ok(())
+            };
+            scaffold::handle(puzzle, overwrite, &template)?;
+            downloaded?;
+            // needs the puzzle description, so only runs after downloading.
+            if from_puzzle {
+                scaffold::handle_examples(puzzle, overwrite)?;
             }
-        },
-    };
+            Ok(())
+        }
+        AppArguments::Solve {
+            year,
+            day,
+            release,
+            dhat,
+            submit,
+            in_process,
+            variants,
+            input,
+        } => solve::handle(
+            PuzzleId::new(year, day),
+            release,
+            dhat,
+            submit,
+            in_process,
+            variants,
+            input,
+        ),
+        AppArguments::Watch { year, day, release } => {
+            watch::handle(PuzzleId::new(year, day), release)
+        }
+        AppArguments::Verify {
+            year,
+            day,
+            execution,
+            limits,
+            record,
+        } => verify::handle(year, day, execution, limits, record),
+        AppArguments::LintSolutions { year, day, limits } => {
+            lint_solutions::handle(year, day, limits)
+        }
+        #[cfg(feature = "today")]
+        AppArguments::Today { year } => {
+            let Some(day) = Day::today(year) else {
+                return Err(Error::Usage(format!(
+                    "`today` command can only be run between the 1st and the {}th of december \
+                    for {year}. Please use `scaffold` with a specific day.",
+                    year.last_day()
+                )));
+            };
+
+            let puzzle = PuzzleId::new(year, day);
+            let downloaded = download::handle(puzzle);
+            scaffold::handle(puzzle, false, &config::get().scaffold.template)?;
+            downloaded?;
+            read::handle(puzzle)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::args::{parse_args, AppArguments};
+    use advent_of_code::template::Execution;
+    use std::time::Duration;
+
+    fn parse(args: &[&str]) -> AppArguments {
+        parse_args(pico_args::Arguments::from_vec(
+            args.iter().map(Into::into).collect(),
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_limits_before_day() {
+        let AppArguments::Time {
+            all, day, limits, ..
+        } = parse(&["time", "--all", "--timeout", "30"])
+        else {
+            panic!("expected the time command");
+        };
+        assert!(all);
+        assert_eq!(day, None);
+        assert_eq!(limits.day_timeout, Some(Duration::from_secs(30)));
+
+        let AppArguments::Time { day, limits, .. } = parse(&["time", "5", "--timeout", "30"])
+        else {
+            panic!("expected the time command");
+        };
+        assert_eq!(day.map(|day| day.into_inner()), Some(5));
+        assert_eq!(limits.day_timeout, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn benches_serially_unless_requested() {
+        let execution = |args: &[&str]| {
+            let AppArguments::Time { execution, .. } = parse(args) else {
+                panic!("expected the time command");
+            };
+            execution
+        };
+
+        assert_eq!(execution(&["time"]), Execution::Serial);
+        assert_eq!(
+            execution(&["time", "--jobs", "4"]),
+            Execution::Parallel { jobs: 1 }
+        );
+        assert_eq!(
+            execution(&["time", "--jobs", "4", "--parallel-bench"]),
+            Execution::Parallel { jobs: 4 }
+        );
+
+        let args = ["time", "--parallel-bench"].map(Into::into).to_vec();
+        assert!(parse_args(pico_args::Arguments::from_vec(args)).is_err());
+    }
 }
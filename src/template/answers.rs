@@ -0,0 +1,124 @@
+/// Store of the known correct answers of each day, e.g. `data/2024/answers/01.json`.
+/// Answers are added after a successful submit and are checked by the `verify` command.
+use std::{collections::HashMap, fs, io, path::Path, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::PuzzleId;
+
+fn get_answers_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path("answers", "json")
+}
+
+/// The known answers of a single day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    /// Rehydrate the answers of `puzzle`. If not present, returns empty answers.
+    pub fn read_from_file(puzzle: PuzzleId) -> Result<Self, String> {
+        match fs::read_to_string(get_answers_path(puzzle)) {
+            Ok(s) => Answers::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Dehydrate the answers of `puzzle`.
+    pub fn store_file(&self, puzzle: PuzzleId) -> io::Result<()> {
+        let path = get_answers_path(puzzle);
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
+        JsonValue::from(self).format_to(&mut file)
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_1 = Some(answer.into()),
+            2 => self.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+}
+
+/// Adds a correct answer to the store of `puzzle`.
+pub fn store_answer(puzzle: PuzzleId, part: u8, answer: &str) -> Result<(), String> {
+    let mut answers = Answers::read_from_file(puzzle)?;
+    answers.set(part, answer);
+    answers.store_file(puzzle).map_err(|e| e.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let optional = |s: &Option<String>| s.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("part_1".into(), optional(&value.part_1));
+        map.insert("part_2".into(), optional(&value.part_2));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).map_err(|x| x.to_string())?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected answers to be a JSON object.")?;
+
+        let optional = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+            Some(_) => Err(format!("expected answers.{key} to be null or string.")),
+        };
+
+        Ok(Answers {
+            part_1: optional("part_1")?,
+            part_2: optional("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_answers() {
+        let mut answers = Answers::default();
+        answers.set(2, "4");
+        assert_eq!(answers.get(1), None);
+        assert_eq!(answers.get(2), Some("4"));
+
+        let s = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(s).unwrap(), answers);
+    }
+
+    #[test]
+    fn parses_partial_answers() {
+        let answers = Answers::try_from(r#"{ "part_1": "11" }"#.to_string()).unwrap();
+        assert_eq!(answers.get(1), Some("11"));
+        assert_eq!(answers.get(2), None);
+        assert!(Answers::try_from(r#"{ "part_1": 11 }"#.to_string()).is_err());
+    }
+}
@@ -0,0 +1,326 @@
+/// Native Advent of Code client, an alternative to the "aoc-cli" command-line.
+/// Enabled with the `client` feature.
+///
+/// The session cookie is read from the `ADVENT_OF_CODE_SESSION` environment variable or from
+/// `~/.adventofcode.session`, the same places aoc-cli reads it from. Set `AOC_BASE_URL` to talk
+/// to a different server.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::PuzzleId;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const SESSION_ENV: &str = "ADVENT_OF_CODE_SESSION";
+const SESSION_FILE: &str = ".adventofcode.session";
+
+#[derive(Debug)]
+pub enum Error {
+    MissingSession,
+    Http(String),
+    Status(u16),
+    IO(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingSession => write!(
+                f,
+                "no session cookie found. Set {SESSION_ENV} or write it to ~/{SESSION_FILE}."
+            ),
+            Error::Http(e) => write!(f, "request failed: {e}"),
+            Error::Status(400 | 500) => {
+                write!(
+                    f,
+                    "request failed with a bad request, is the session cookie valid?"
+                )
+            }
+            Error::Status(404) => write!(f, "puzzle not found, is it unlocked yet?"),
+            Error::Status(status) => write!(f, "request failed with status {status}."),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<ureq::Error> for Error {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => Error::Status(status),
+            ureq::Error::Transport(e) => Error::Http(e.to_string()),
+        }
+    }
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(concat!("advent_of_code/", env!("CARGO_PKG_VERSION")))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+        }
+    }
+
+    /// A client for `AOC_BASE_URL` (or adventofcode.com) with the session of the current user.
+    pub fn from_env() -> Result<Self, Error> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &read_session()?))
+    }
+
+    fn day_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, Error> {
+        let response = self
+            .agent
+            .get(&format!("{}/input", self.day_url(puzzle)))
+            .set("Cookie", &self.cookie())
+            .call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// The puzzle description as markdown. Includes part two once it is unlocked.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, Error> {
+        let response = self
+            .agent
+            .get(&self.day_url(puzzle))
+            .set("Cookie", &self.cookie())
+            .call()?;
+        let html = response.into_string()?;
+
+        Ok(extract_articles(&html)
+            .into_iter()
+            .filter(|article| article.contains("day-desc"))
+            .map(|article| html2md::parse_html(article).trim().to_string())
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    }
+
+    /// Submits an answer and returns the server's response as text.
+    pub fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, Error> {
+        let level = part.to_string();
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(puzzle)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &level), ("answer", answer)])?;
+        let html = response.into_string()?;
+
+        Ok(extract_articles(&html)
+            .into_iter()
+            .map(|article| html2md::parse_html(article).trim().to_string())
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+}
+
+fn read_session() -> Result<String, Error> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        return Ok(session);
+    }
+
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .ok_or(Error::MissingSession)?;
+
+    fs::read_to_string(PathBuf::from(home).join(SESSION_FILE)).map_err(|_| Error::MissingSession)
+}
+
+/// All `<article>` elements of a page. Puzzle pages have one per part, answer pages a single one.
+fn extract_articles(html: &str) -> Vec<&str> {
+    const END_TAG: &str = "</article>";
+
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(len) = rest[start..].find(END_TAG) else {
+            break;
+        };
+        let end = start + len + END_TAG.len();
+        articles.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+
+    articles
+}
+
+fn write_file(path: &str, contents: &str) -> Result<(), Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    Ok(fs::write(path, contents)?)
+}
+
+/// Downloads the input and puzzle description of a day.
+pub fn download(puzzle: PuzzleId) -> Result<(), Error> {
+    let client = Client::from_env()?;
+    let input_path = puzzle.data_path("inputs", "txt");
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    write_file(&input_path, &client.input(puzzle)?)?;
+    write_file(&puzzle_path, &client.puzzle(puzzle)?)?;
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Downloads and prints the puzzle description of a day.
+pub fn read(puzzle: PuzzleId) -> Result<(), Error> {
+    let description = Client::from_env()?.puzzle(puzzle)?;
+    write_file(&puzzle.data_path("puzzles", "md"), &description)?;
+    println!("{description}");
+    Ok(())
+}
+
+/// Submits an answer, prints the response and returns it.
+pub fn submit(puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, Error> {
+    let response = Client::from_env()?.submit(puzzle, part, answer)?;
+    println!("{response}");
+    Ok(response)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract_articles, Client, Error};
+    use crate::template::{Day, PuzzleId, Year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serves a single request with `status` and `body`, returning the raw request.
+    fn stub_server(status: u16, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body_buf = vec![0; content_length];
+            reader.read_exact(&mut body_buf).unwrap();
+            request.push_str(&String::from_utf8(body_buf).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+        });
+
+        (url, handle)
+    }
+
+    fn puzzle() -> PuzzleId {
+        PuzzleId::new(Year::new(2024).unwrap(), Day::new(5).unwrap())
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (url, server) = stub_server(200, "1 2\n3 4\n");
+        let input = Client::new(&url, "abc\n").input(puzzle()).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(input, "1 2\n3 4\n");
+        assert!(request.starts_with("GET /2024/day/5/input "));
+        assert!(request.contains("session=abc\r\n"));
+    }
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let (url, server) = stub_server(
+            200,
+            r#"<main><article class="day-desc"><h2>--- Day 5 ---</h2><p>Some <em>text</em>.</p></article><p>Answer: <code>1</code></p><article class="day-desc"><p>Part two.</p></article></main>"#,
+        );
+        let description = Client::new(&url, "abc").puzzle(puzzle()).unwrap();
+        server.join().unwrap();
+
+        assert!(description.contains("--- Day 5 ---"));
+        assert!(description.contains("Some *text*."));
+        assert!(description.contains("Part two."));
+        assert!(!description.contains("Answer"));
+    }
+
+    #[test]
+    fn posts_answers() {
+        let (url, server) = stub_server(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too high.</p></article></main>",
+        );
+        let response = Client::new(&url, "abc").submit(puzzle(), 2, "42").unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(
+            response,
+            "That's not the right answer; your answer is too high."
+        );
+        assert!(request.starts_with("POST /2024/day/5/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (url, server) = stub_server(404, "");
+        let result = Client::new(&url, "abc").input(puzzle());
+        server.join().unwrap();
+        assert!(matches!(result, Err(Error::Status(404))));
+    }
+
+    #[test]
+    fn extracts_articles() {
+        let html = "<article>a</article> <article class=\"x\">b</article><article>";
+        assert_eq!(
+            extract_articles(html),
+            vec!["<article>a</article>", "<article class=\"x\">b</article>"]
+        );
+    }
+}
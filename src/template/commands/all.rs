@@ -1,5 +1,19 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::run_multi::{run_multi, Measurement};
+use crate::template::{all_days, Error, Execution, Limits, Year};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(
+    year: Year,
+    is_release: bool,
+    execution: Execution,
+    limits: Limits,
+) -> Result<(), Error> {
+    run_multi(
+        year,
+        &all_days(year).collect(),
+        is_release,
+        Measurement::Run,
+        execution,
+        limits,
+    )?;
+    Ok(())
 }
@@ -1,14 +1,15 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{Error, PuzzleId};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+#[cfg(not(feature = "client"))]
+pub fn handle(puzzle: PuzzleId) -> Result<(), Error> {
+    use crate::template::aoc_cli;
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    aoc_cli::check()?;
+    aoc_cli::download(puzzle)?;
+    Ok(())
+}
+
+#[cfg(feature = "client")]
+pub fn handle(puzzle: PuzzleId) -> Result<(), Error> {
+    Ok(crate::template::aoc_client::download(puzzle)?)
 }
@@ -0,0 +1,277 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::run_multi::child_commands::{build_solutions, run_built_solution};
+use crate::template::run_multi::Measurement;
+use crate::template::runner::InputSource;
+use crate::template::{all_days, data_dir, Day, Error, Limits, PuzzleId, Year};
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// Wall-clock timeout of a part if no timeout is given. An empty input can send a solution into
+/// an endless loop.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An input the parts of a day are run against.
+struct Probe {
+    name: String,
+    input: InputSource,
+}
+
+/// Outcome of comparing the answers of one part across its inputs.
+#[derive(Debug, PartialEq, Eq)]
+enum Lint {
+    /// The answer depends on the input.
+    Varies,
+    /// Every input gave the same answer.
+    Constant(String),
+    /// The empty input gave the same answer as a real one.
+    IgnoresInput(String),
+    /// Only one input gave an answer, so there is nothing to compare.
+    Inconclusive,
+}
+
+/// Runs every part against its puzzle input, its examples and an empty input, and flags the
+/// parts that give the same answer regardless of their input.
+/// Returns [`Error::InputIgnored`] if any part is flagged.
+pub fn handle(year: Year, day: Option<Day>, limits: Limits) -> Result<(), Error> {
+    let days: Vec<Day> = all_days(year)
+        .filter(|d| day.is_none_or(|day| day == *d))
+        .filter(|day| Path::new(&PuzzleId::new(year, *day).bin_path()).exists())
+        .collect();
+
+    if days.is_empty() {
+        println!("No solutions to lint.");
+        return Ok(());
+    }
+
+    let limits = Limits {
+        day_timeout: limits
+            .day_timeout
+            .or(limits.part_timeout)
+            .or(Some(DEFAULT_TIMEOUT)),
+        ..limits
+    };
+
+    build_solutions(true, Measurement::Run)?;
+
+    println!("{ANSI_BOLD}Input sensitivity{ANSI_RESET}");
+
+    let mut flagged = 0;
+
+    for day in days {
+        let puzzle = PuzzleId::new(year, day);
+        let probes = probes(puzzle);
+
+        for part in 1..=2 {
+            // parts run on their own, so that a panic in part one does not hide part two.
+            let mut answers = vec![];
+            for probe in &probes {
+                let output = run_built_solution(
+                    puzzle,
+                    Measurement::Run,
+                    true,
+                    &probe.input,
+                    Some(part),
+                    &limits,
+                )?;
+                if let Some(interruption) = output.run.interruption {
+                    println!("Day {day} Part {part}: ✖ {interruption} on {}", probe.name);
+                }
+
+                let answer = output
+                    .run
+                    .records
+                    .iter()
+                    .find(|r| r.part == part && r.is_solved())
+                    .and_then(|r| r.answer.clone());
+                answers.push(answer);
+            }
+
+            // the empty input is always probed last.
+            let Some((empty, answers)) = answers.split_last() else {
+                continue;
+            };
+
+            let line = match lint_part(answers, empty.as_deref()) {
+                None => continue,
+                Some(Lint::Varies) => "✔ depends on the input".into(),
+                Some(Lint::Constant(answer)) => {
+                    flagged += 1;
+                    format!("✖ answer never changes ({answer})")
+                }
+                Some(Lint::IgnoresInput(answer)) => {
+                    flagged += 1;
+                    format!("✖ returns without reading the input ({answer})")
+                }
+                Some(Lint::Inconclusive) => "? only one input gave an answer".into(),
+            };
+            println!("Day {day} Part {part}: {line}");
+        }
+    }
+
+    if flagged > 0 {
+        return Err(Error::InputIgnored { parts: flagged });
+    }
+
+    Ok(())
+}
+
+/// The inputs of a day that exist: the puzzle input, the example, additional examples such as
+/// `data/2024/examples/01-2.txt` and, last, an empty input.
+fn probes(puzzle: PuzzleId) -> Vec<Probe> {
+    let mut probes = vec![];
+
+    if Path::new(&puzzle.data_path("inputs", "txt")).exists() {
+        probes.push(Probe {
+            name: "input".into(),
+            input: InputSource::Puzzle,
+        });
+    }
+
+    // scaffolding creates an empty example, which is covered by the empty input.
+    let example = fs::read_to_string(puzzle.data_path("examples", "txt")).unwrap_or_default();
+    if !example.trim().is_empty() {
+        probes.push(Probe {
+            name: "example".into(),
+            input: InputSource::Example,
+        });
+    }
+
+    let examples_dir = format!("{}/examples", data_dir(puzzle.year));
+    probes.extend(
+        additional_examples(Path::new(&examples_dir), puzzle.day)
+            .into_iter()
+            .map(|path| Probe {
+                name: path.to_string_lossy().into(),
+                // solutions with parameters run these with their example parameters.
+                input: InputSource::ExampleFile(path),
+            }),
+    );
+
+    // solution binaries run with an empty standard input.
+    probes.push(Probe {
+        name: "empty input".into(),
+        input: InputSource::Stdin,
+    });
+
+    probes
+}
+
+/// The additional examples of `day` in `examples_dir`, e.g. `01-2.txt`, sorted by name.
+fn additional_examples(examples_dir: &Path, day: Day) -> Vec<PathBuf> {
+    let prefix = format!("{day}-");
+    let mut examples: Vec<PathBuf> = fs::read_dir(examples_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".txt"))
+        })
+        .collect();
+    examples.sort();
+    examples
+}
+
+/// Compares the answers of a part for the inputs of a day with its answer for an empty input.
+/// `None` if the part never gave an answer.
+fn lint_part(answers: &[Option<String>], empty: Option<&str>) -> Option<Lint> {
+    let answers: Vec<&str> = answers.iter().flatten().map(String::as_str).collect();
+    let first = *answers.first()?;
+
+    let lint = if let Some(empty) = empty.filter(|empty| answers.contains(empty)) {
+        Lint::IgnoresInput(empty.into())
+    } else if answers.len() < 2 {
+        Lint::Inconclusive
+    } else if answers.iter().all(|answer| *answer == first) {
+        Lint::Constant(first.into())
+    } else {
+        Lint::Varies
+    };
+
+    Some(lint)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{additional_examples, lint_part, Lint};
+    use crate::day;
+    use crate::template::runner::InputSource;
+    use crate::template::PuzzleParams;
+
+    fn answers(answers: &[Option<&str>]) -> Vec<Option<String>> {
+        answers.iter().map(|a| a.map(Into::into)).collect()
+    }
+
+    #[test]
+    fn accepts_answers_that_depend_on_the_input() {
+        let answers = answers(&[Some("1194"), Some("11")]);
+        assert_eq!(lint_part(&answers, Some("0")), Some(Lint::Varies));
+        assert_eq!(lint_part(&answers, None), Some(Lint::Varies));
+    }
+
+    #[test]
+    fn flags_hard_coded_answers() {
+        let constant = answers(&[Some("dpg,kmb"), Some("dpg,kmb")]);
+        assert_eq!(
+            lint_part(&constant, None),
+            Some(Lint::Constant("dpg,kmb".into()))
+        );
+        assert_eq!(
+            lint_part(&constant, Some("dpg,kmb")),
+            Some(Lint::IgnoresInput("dpg,kmb".into()))
+        );
+
+        let single = answers(&[None, Some("4,6,3")]);
+        assert_eq!(
+            lint_part(&single, Some("4,6,3")),
+            Some(Lint::IgnoresInput("4,6,3".into()))
+        );
+    }
+
+    #[test]
+    fn needs_two_answers_to_compare() {
+        assert_eq!(
+            lint_part(&answers(&[Some("11"), None]), None),
+            Some(Lint::Inconclusive)
+        );
+        assert_eq!(lint_part(&answers(&[None, None]), Some("0")), None);
+    }
+
+    /// The grid of day 18, which the additional examples have to run with.
+    #[derive(Debug, PartialEq)]
+    struct Grid(usize);
+
+    impl PuzzleParams for Grid {
+        fn real() -> Self {
+            Grid(70)
+        }
+
+        fn example() -> Self {
+            Grid(6)
+        }
+    }
+
+    #[test]
+    fn runs_additional_examples_with_example_params() {
+        let dir = env::temp_dir().join(format!("aoc-lint-examples-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["18.txt", "18-2.txt", "18-b.txt", "19-2.txt"] {
+            fs::write(dir.join(name), "5,4\n").unwrap();
+        }
+
+        let examples = additional_examples(&dir, day!(18));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(examples, vec![dir.join("18-2.txt"), dir.join("18-b.txt")]);
+        let input = InputSource::ExampleFile(examples[0].clone());
+        assert_eq!(Grid::for_input(&input), Grid(6));
+    }
+}
@@ -1,15 +1,15 @@
-use std::process;
+use crate::template::{Error, PuzzleId};
 
-use crate::template::{aoc_cli, Day};
+#[cfg(not(feature = "client"))]
+pub fn handle(puzzle: PuzzleId) -> Result<(), Error> {
+    use crate::template::aoc_cli;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+    aoc_cli::check()?;
+    aoc_cli::read(puzzle)?;
+    Ok(())
+}
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+#[cfg(feature = "client")]
+pub fn handle(puzzle: PuzzleId) -> Result<(), Error> {
+    Ok(crate::template::aoc_client::read(puzzle)?)
 }
@@ -0,0 +1,45 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::template::report::{render_badge, render_html};
+use crate::template::timing_history::TimingHistory;
+use crate::template::timings::Timings;
+use crate::template::{data_dir, Error, Year};
+
+/// Writes `index.html` and `badge.svg` for the stored timings of `year`.
+/// Defaults to `data/<year>/report`.
+pub fn handle(year: Year, out_dir: Option<PathBuf>) -> Result<(), Error> {
+    let timings = Timings::read_from_file(year);
+
+    if timings.data.is_empty() {
+        return Err(Error::Missing(format!(
+            "No timings stored for {year}. Run `cargo time --store` first."
+        )));
+    }
+
+    let history = TimingHistory::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read timing history, skipping it: {e}");
+        TimingHistory::default()
+    });
+
+    let out_dir = out_dir.unwrap_or_else(|| Path::new(&data_dir(year)).join("report"));
+    let html_path = out_dir.join("index.html");
+    let badge_path = out_dir.join("badge.svg");
+
+    let badge = render_badge(
+        &format!("AoC {year}"),
+        &format!("{:.2}ms", timings.total_millis()),
+    );
+
+    fs::create_dir_all(&out_dir)
+        .and_then(|()| fs::write(&html_path, render_html(year, &timings, &history)))
+        .and_then(|()| fs::write(&badge_path, badge))
+        .map_err(|source| Error::File {
+            path: out_dir.display().to_string(),
+            source,
+        })?;
+
+    println!("Wrote report to \"{}\".", html_path.display());
+    println!("Wrote badge to \"{}\".", badge_path.display());
+    Ok(())
+}
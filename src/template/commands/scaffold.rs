@@ -1,13 +1,48 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
 };
 
-use crate::template::Day;
+use crate::template::config;
+use crate::template::puzzle_examples::{puzzle_title, PuzzleExamples};
+use crate::template::{data_dir, Error, PuzzleId, Year};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Templates that ship with this repository, see `src/templates`.
+/// Templates of the same name in `scaffold.templates_dir` take precedence.
+const BUILT_IN_TEMPLATES: [(&str, &str); 4] = [
+    (
+        "default",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/default.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "graph",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/graph.txt"
+        )),
+    ),
+    (
+        "params",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/params.txt"
+        )),
+    ),
+];
+
+/// The assertion of a test in the templates before an expected answer is filled in.
+const EMPTY_ASSERTION: &str = "assert_eq!(result, None);";
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -19,61 +54,275 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Creates an empty data file. Returns `false` if the file already exists, e.g. a downloaded input.
+fn create_file(path: &str) -> Result<bool, std::io::Error> {
+    // data folders of a new year do not exist yet.
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Adds the path of the file to an IO error.
+fn file_error(path: &str) -> impl FnOnce(io::Error) -> Error + '_ {
+    move |source| Error::File {
+        path: path.into(),
+        source,
+    }
+}
+
+/// Loads a template by name, preferring project-local templates over the built-in ones.
+fn load_template(name: &str) -> Result<String, Error> {
+    let local_path = format!("{}/{name}.txt", config::get().scaffold.templates_dir);
+
+    match fs::read_to_string(&local_path) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => BUILT_IN_TEMPLATES
+            .iter()
+            .find(|(built_in, _)| *built_in == name)
+            .map(|(_, template)| (*template).to_string())
+            .ok_or_else(|| {
+                Error::Missing(format!(
+                    "Unknown template `{name}`. Available templates: {}.",
+                    template_names().join(", ")
+                ))
+            }),
+        Err(e) => Err(file_error(&local_path)(e)),
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Names of all built-in and project-local templates.
+fn template_names() -> Vec<String> {
+    let mut names: Vec<String> = BUILT_IN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .collect();
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+    if let Ok(entries) = fs::read_dir(&config::get().scaffold.templates_dir) {
+        for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+            if path.extension().is_some_and(|extension| extension == "txt") {
+                if let Some(name) = path.file_stem() {
+                    names.push(name.to_string_lossy().into());
+                }
+            }
         }
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Replaces the placeholders of a template.
+/// The title is taken from the puzzle description, if it was downloaded already.
+fn render_template(template: &str, puzzle: PuzzleId) -> String {
+    let title = fs::read_to_string(puzzle.data_path("puzzles", "md"))
+        .ok()
+        .and_then(|markdown| puzzle_title(&markdown))
+        .unwrap_or_else(|| format!("Day {}", puzzle.day.into_inner()));
+
+    template
+        .replace("%DAY_NUMBER%", &puzzle.day.into_inner().to_string())
+        .replace("%DAY%", &puzzle.day.to_string())
+        .replace("%YEAR%", &puzzle.year.to_string())
+        .replace("%TITLE%", &title)
+}
+
+/// Creates the module of a day from `template`, see `scaffold.template` in `aoc.toml`.
+pub fn handle(puzzle: PuzzleId, overwrite: bool, template: &str) -> Result<(), Error> {
+    let day = puzzle.day;
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = puzzle.bin_path();
+
+    let module = render_template(&load_template(template)?, puzzle);
+
+    let mut file = safe_create_file(&module_path, overwrite).map_err(file_error(&module_path))?;
+    file.write_all(module.as_bytes())
+        .map_err(file_error(&module_path))?;
+    println!("Created module file \"{}\"", &module_path);
+
+    if create_file(&input_path).map_err(file_error(&input_path))? {
+        println!("Created empty input file \"{}\"", &input_path);
+    }
+
+    if create_file(&example_path).map_err(file_error(&example_path))? {
+        println!("Created empty example file \"{}\"", &example_path);
+    }
+
+    println!("---");
+    let year_arg = if puzzle.year == Year::current() {
+        String::new()
+    } else {
+        format!(" --year {}", puzzle.year)
     };
+    println!("🎄 Type `cargo solve {day}{year_arg}` to run your solution.");
+    Ok(())
+}
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+/// Writes the examples of the downloaded puzzle description to the example files and
+/// fills in the expected answers of the tests (`--from-puzzle`).
+/// Example files that are not empty are only replaced with `overwrite`. Only tests that still
+/// have the empty assertion of the template are filled in.
+pub fn handle_examples(puzzle: PuzzleId, overwrite: bool) -> Result<(), Error> {
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+    let module_path = puzzle.bin_path();
+
+    let markdown = fs::read_to_string(&puzzle_path).map_err(|_| {
+        Error::Missing(format!(
+            "No puzzle description at \"{puzzle_path}\". Run `cargo download {}` first.",
+            puzzle.day
+        ))
+    })?;
+    let examples = PuzzleExamples::parse(&markdown);
+
+    if examples.is_empty() {
+        println!("No examples found in \"{puzzle_path}\".");
+        return Ok(());
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    let example_paths = [
+        (puzzle.data_path("examples", "txt"), &examples.example),
+        (
+            format!("{}/examples/{}-2.txt", data_dir(puzzle.year), puzzle.day),
+            &examples.example_part_two,
+        ),
+    ];
+
+    for (path, example) in example_paths {
+        let Some(example) = example else {
+            continue;
+        };
+
+        let is_empty = fs::read_to_string(&path).map_or(true, |s| s.trim().is_empty());
+        if !is_empty && !overwrite {
+            println!("Kept the existing example \"{path}\", pass --overwrite to replace it");
+            continue;
         }
+
+        fs::write(&path, example).map_err(file_error(&path))?;
+        println!("Wrote example to \"{path}\"");
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    let module = fs::read_to_string(&module_path).map_err(file_error(&module_path))?;
+    fs::write(&module_path, fill_tests(&module, &examples)).map_err(file_error(&module_path))?;
+
+    for (part, answer) in [
+        (1, &examples.answer_part_one),
+        (2, &examples.answer_part_two),
+    ] {
+        match answer {
+            Some(answer) if is_number(answer) => {
+                println!("Expecting {answer} for the example of part {part}");
+            }
+            Some(answer) => {
+                println!("Expecting \"{answer}\" for the example of part {part}, compared as text");
+            }
+            None => {}
         }
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    Ok(())
+}
+
+/// Replaces the empty assertions of the tests in a module with the expected answers.
+/// Answers that are not numbers, e.g. `6,1`, are compared with the formatted result, as the
+/// template returns `Option<usize>`.
+fn fill_tests(module: &str, examples: &PuzzleExamples) -> String {
+    let (part_one, part_two) = match module.find("fn test_part_two") {
+        Some(index) => module.split_at(index),
+        None => (module, ""),
+    };
+
+    let fill = |test: &str, answer: &Option<String>| match answer {
+        Some(answer) if is_number(answer) => test.replacen(
+            EMPTY_ASSERTION,
+            &format!("assert_eq!(result, Some({answer}));"),
+            1,
+        ),
+        Some(answer) => test.replacen(
+            EMPTY_ASSERTION,
+            &format!("assert_eq!(result.map(|r| r.to_string()).as_deref(), Some({answer:?}));"),
+            1,
+        ),
+        None => test.to_string(),
+    };
+
+    let mut part_two = fill(part_two, &examples.answer_part_two);
+    if examples.example_part_two.is_some() {
+        part_two = part_two.replacen(
+            "read_file(\"examples\", DAY)",
+            "read_file_part(\"examples\", DAY, 2)",
+            1,
+        );
+    }
+
+    fill(part_one, &examples.answer_part_one) + &part_two
+}
+
+fn is_number(answer: &str) -> bool {
+    answer.parse::<u64>().is_ok()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fill_tests, render_template, BUILT_IN_TEMPLATES};
+    use crate::template::puzzle_examples::PuzzleExamples;
+    use crate::template::{Day, PuzzleId, Year};
+
+    const MODULE_TEMPLATE: &str = BUILT_IN_TEMPLATES[0].1;
+
+    #[test]
+    fn fills_expected_answers() {
+        let examples = PuzzleExamples {
+            example: Some("1\n".into()),
+            example_part_two: Some("2\n".into()),
+            answer_part_one: Some("11".into()),
+            answer_part_two: Some("a,b".into()),
+        };
+        let module = fill_tests(MODULE_TEMPLATE, &examples);
+
+        assert!(module.contains("assert_eq!(result, Some(11));"));
+        assert!(
+            module.contains("assert_eq!(result.map(|r| r.to_string()).as_deref(), Some(\"a,b\"));")
+        );
+        assert!(!module.contains("assert_eq!(result, None);"));
+        assert!(module
+            .contains("part_two(&advent_of_code::template::read_file_part(\"examples\", DAY, 2))"));
+        assert_eq!(
+            fill_tests(MODULE_TEMPLATE, &PuzzleExamples::default()),
+            MODULE_TEMPLATE
+        );
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let puzzle = PuzzleId::new(Year::new(2015).unwrap(), Day::new(7).unwrap());
+        let module = render_template(BUILT_IN_TEMPLATES[1].1, puzzle);
+
+        assert!(module.starts_with("// Day 7 (2015)\nadvent_of_code::solution!(7);"));
+        assert!(!module.contains('%'));
+        assert_eq!(render_template("%DAY%", puzzle), "07");
+    }
+
+    #[test]
+    fn fills_tests_of_params_template() {
+        let examples = PuzzleExamples {
+            example_part_two: Some("2\n".into()),
+            answer_part_one: Some("11".into()),
+            ..PuzzleExamples::default()
+        };
+        let module = fill_tests(BUILT_IN_TEMPLATES[3].1, &examples);
+
+        assert!(module.contains("assert_eq!(result, Some(11));"));
+        assert!(module
+            .contains("read_file_part(\"examples\", DAY, 2),\n            &Params::example()"));
+    }
 }
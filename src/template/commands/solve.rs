@@ -1,9 +1,48 @@
+use std::path::Path;
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::runner::{InputSource, RunOptions};
+use crate::template::{registry, Error, PuzzleId};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    in_process: bool,
+    variants: bool,
+    input: InputSource,
+) -> Result<(), Error> {
+    if submit_part.is_some() && input != InputSource::Puzzle {
+        return Err(Error::Usage(
+            "`--submit` can only be used with the puzzle input.".into(),
+        ));
+    }
+
+    if variants && (dhat || submit_part.is_some()) {
+        return Err(Error::Usage(
+            "`--variants` can not be combined with `--dhat` or `--submit`.".into(),
+        ));
+    }
+
+    if in_process {
+        if dhat || submit_part.is_some() || variants {
+            return Err(Error::Usage(
+                "`--in-process` can not be combined with `--dhat`, `--submit` or `--variants`."
+                    .into(),
+            ));
+        }
+        return run_in_process(puzzle, input);
+    }
+
+    if !Path::new(&puzzle.bin_path()).exists() {
+        return Err(Error::Missing(format!(
+            "Day {} of {} does not exist. Did you scaffold it?",
+            puzzle.day, puzzle.year
+        )));
+    }
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -12,10 +51,16 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
+    } else if release || variants {
+        // benches of debug builds are not meaningful.
         cmd_args.push("--release".to_string());
     }
 
+    // submit with the same client as this binary.
+    if cfg!(feature = "client") {
+        cmd_args.extend(["--features".to_string(), "client".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,12 +68,54 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    if variants {
+        cmd_args.push("--variants".to_string());
+    }
+
+    cmd_args.extend(input.to_args());
+
+    let status = Command::new("cargo")
         .args(&cmd_args)
+        .env("AOC_YEAR", puzzle.year.to_string())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()?;
+
+    if !status.success() {
+        return Err(Error::Child {
+            command: "cargo run".into(),
+            status,
+        });
+    }
+
+    Ok(())
+}
+
+/// Runs a solution that was compiled into this binary by the `registry` feature.
+fn run_in_process(puzzle: PuzzleId, input: InputSource) -> Result<(), Error> {
+    if !registry::IS_ENABLED {
+        return Err(Error::Usage(
+            "Running solutions in-process requires the `registry` feature.".into(),
+        ));
+    }
+
+    let Some(solution) = registry::find(puzzle) else {
+        return Err(Error::Missing(format!(
+            "Day {} of {} is not registered. Did you scaffold it?",
+            puzzle.day, puzzle.year
+        )));
+    };
+
+    let options = RunOptions {
+        input,
+        ..RunOptions::default()
+    };
+
+    let input = options.input.read(puzzle).map_err(Error::Input)?;
+
+    for part in solution.phases() {
+        (solution.run)(&input, part, &options)?;
+    }
 
-    cmd.wait().unwrap();
+    Ok(())
 }
@@ -1,19 +1,43 @@
 use std::collections::HashSet;
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::protocol::PartRecord;
+use crate::template::run_multi::{run_multi, Measurement};
+use crate::template::timing_history::{sparkline, HistoryEntry, TimingHistory};
+use crate::template::timings::{format_nanos, PartComparison, Timings};
+use crate::template::{
+    all_days, readme_benchmarks, Day, Error, Execution, Limits, StatColumn, Year,
+};
+use crate::template::{ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Runs benchmarks. If `compare_threshold` is set, the fresh timings are compared against
+/// the stored ones and [`Error::Regressed`] is returned if any part regressed by more than
+/// the threshold (in percent). Regressed timings are not stored.
+/// With `memory`, the heap usage of every part is profiled in a separate run after benching,
+/// so that the profiler does not slow down the benchmarks.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    stat_columns: &[StatColumn],
+    compare_threshold: Option<f64>,
+    memory: bool,
+    execution: Execution,
+    limits: Limits,
+) -> Result<(), Error> {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                all_days(year).collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, bench every day that has a baseline.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                all_days(year)
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,14 +45,63 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut timings = run_multi(
+        year,
+        &days_to_run,
+        true,
+        Measurement::Time,
+        execution,
+        limits,
+    )?
+    .timings
+    .unwrap();
 
-    if store {
+    // in-process solutions are profiled by this binary's allocator.
+    let memory = if memory && execution == Execution::InProcess && !cfg!(feature = "dhat-heap") {
+        eprintln!("Measuring memory in-process requires the `dhat-heap` feature.");
+        false
+    } else {
+        memory
+    };
+
+    if memory {
+        println!("\n{ANSI_BOLD}Measuring memory{ANSI_RESET}\n");
+        let run = run_multi(
+            year,
+            &days_to_run,
+            true,
+            Measurement::Memory,
+            execution,
+            limits,
+        )?;
+        add_memory_stats(&mut timings, &run.records);
+    }
+
+    let regressions = compare_threshold.map(|threshold| {
+        (
+            print_comparison(&timings.compare(&stored_timings), threshold),
+            threshold,
+        )
+    });
+    let is_regressed = regressions.is_some_and(|(parts, _)| parts > 0);
+
+    // a regressed run would become the baseline of the next comparison.
+    if store && is_regressed {
+        eprintln!("\nNot storing the benchmarks, because parts regressed.");
+    } else if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year)?;
+
+        // in-process solutions are compiled into this binary.
+        let profile = if execution == Execution::InProcess && cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        };
+        append_history(year, HistoryEntry::new(&timings, profile));
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings, stat_columns) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +110,132 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    match regressions {
+        Some((parts, threshold)) if parts > 0 => Err(Error::Regressed { parts, threshold }),
+        _ => Ok(()),
+    }
+}
+
+/// Prints how the runtime of every part of `day` changed across the stored runs.
+pub fn handle_history(year: Year, day: Day) -> Result<(), Error> {
+    let history = TimingHistory::read_from_file(year)
+        .map_err(|e| Error::Parse(format!("Failed to read timing history: {e}")))?;
+
+    let entries: Vec<&HistoryEntry> = history.for_day(day).collect();
+
+    if entries.is_empty() {
+        println!("No stored timings for day {day}. Run `cargo time {day} --store` first.");
+        return Ok(());
+    }
+
+    println!(
+        "{ANSI_BOLD}Day {day}{ANSI_RESET} timing history ({} runs)",
+        entries.len()
+    );
+
+    for part in 1..=2 {
+        let timings: Vec<(&HistoryEntry, f64)> = entries
+            .iter()
+            .filter_map(|entry| Some((*entry, entry.part(day, part)?.mean_nanos)))
+            .collect();
+
+        let (Some((_, first)), Some((_, last))) = (timings.first(), timings.last()) else {
+            continue;
+        };
+
+        let values: Vec<f64> = timings.iter().map(|(_, nanos)| *nanos).collect();
+        println!(
+            "\nPart {part}: {}  {} → {}",
+            sparkline(&values),
+            format_nanos(*first),
+            format_nanos(*last)
+        );
+
+        for (entry, nanos) in &timings {
+            println!(
+                "  {}  {:<13}  {:<12}  {:<7}  {}",
+                entry.date(),
+                entry.commit.as_deref().unwrap_or("-"),
+                entry.machine,
+                entry.profile,
+                format_nanos(*nanos)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Appends a stored run to the timing history of `year`.
+fn append_history(year: Year, entry: HistoryEntry) {
+    // do not overwrite a history that could not be read.
+    let result = TimingHistory::read_from_file(year).and_then(|mut history| {
+        history.entries.push(entry);
+        history.store_file(year).map_err(|e| e.to_string())
+    });
+
+    if let Err(e) = result {
+        eprintln!("Failed to store timing history: {e}");
+    }
+}
+
+/// Copies the heap usage of every profiled part into its timing.
+fn add_memory_stats(timings: &mut Timings, records: &[PartRecord]) {
+    for record in records.iter().filter(|record| record.is_solved()) {
+        let timing = timings
+            .data
+            .iter_mut()
+            .find(|timing| timing.day == record.day)
+            .and_then(|timing| match record.part {
+                1 => timing.part_1.as_mut(),
+                2 => timing.part_2.as_mut(),
+                _ => None,
+            });
+
+        if let Some(timing) = timing {
+            timing.memory.clone_from(&record.timing.memory);
+        }
+    }
+}
+
+/// Prints the comparison of every part and returns the number of parts that regressed.
+fn print_comparison(comparisons: &[PartComparison], threshold: f64) -> usize {
+    println!(
+        "\n{ANSI_BOLD}Comparison against stored timings{ANSI_RESET} (threshold: {threshold}%)"
+    );
+
+    if comparisons.is_empty() {
+        println!("No stored timings to compare against.");
+        return 0;
+    }
+
+    let mut regressions = 0;
+
+    for comparison in comparisons {
+        let is_regression = comparison.is_regression(threshold);
+        if is_regression {
+            regressions += 1;
+        }
+
+        let change = match (comparison.current_nanos, comparison.ratio()) {
+            (Some(current), Some(ratio)) if ratio <= 1_f64 => {
+                format!("{} ({:.2}x faster)", format_nanos(current), 1_f64 / ratio)
+            }
+            (Some(current), Some(ratio)) => {
+                format!("{} ({ratio:.2}x slower)", format_nanos(current))
+            }
+            _ => "not solved".into(),
+        };
+
+        println!(
+            "Day {} Part {}: {} → {change}{}",
+            comparison.day,
+            comparison.part,
+            format_nanos(comparison.baseline_nanos),
+            if is_regression { " ✖" } else { "" }
+        );
+    }
+
+    regressions
 }
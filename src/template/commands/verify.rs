@@ -0,0 +1,307 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::template::answers::Answers;
+use crate::template::protocol::PartRecord;
+use crate::template::run_multi::{run_multi, Measurement};
+use crate::template::{all_days, Day, Error, Execution, Limits, PuzzleId, Year};
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// Outcome of checking one part against the answer store.
+#[derive(Debug, PartialEq, Eq)]
+enum Check {
+    Match(String),
+    Mismatch {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// The part produced an answer, but there is no known answer to compare it with.
+    MissingAnswer(String),
+    /// The part returned an error.
+    Failed(String),
+}
+
+/// Runs every day that has a solution and compares the results against the stored answers.
+/// Returns [`Error::Mismatched`] if any part does not match its stored answer or has none.
+///
+/// With `record`, the answers of the run are stored instead, replacing the stored answers of
+/// the parts that were solved.
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    execution: Execution,
+    limits: Limits,
+    record: bool,
+) -> Result<(), Error> {
+    let days: HashSet<Day> = day.map_or_else(
+        || {
+            all_days(year)
+                .filter(|day| Path::new(&PuzzleId::new(year, *day).bin_path()).exists())
+                .collect()
+        },
+        |day| HashSet::from([day]),
+    );
+
+    if days.is_empty() {
+        println!("No solutions to verify.");
+        return Ok(());
+    }
+
+    let run = run_multi(year, &days, true, Measurement::Run, execution, limits)?;
+
+    if record {
+        return record_answers(year, &days, &run.records);
+    }
+
+    println!("\n{ANSI_BOLD}Verification against stored answers{ANSI_RESET}");
+
+    let mut failures = 0;
+    let mut missing = 0;
+
+    for day in all_days(year).filter(|day| days.contains(day)) {
+        let answers = match Answers::read_from_file(PuzzleId::new(year, day)) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("Day {day}: failed to read stored answers: {e}");
+                failures += 1;
+                continue;
+            }
+        };
+
+        let records: Vec<&PartRecord> = run.records.iter().filter(|r| r.day == day).collect();
+
+        for (part, check) in check_day(&answers, &records) {
+            let line = match check {
+                Check::Match(answer) => format!("✔ {answer}"),
+                Check::Mismatch { expected, actual } => {
+                    failures += 1;
+                    match actual {
+                        Some(actual) => format!("✖ expected {expected}, got {actual}"),
+                        None => format!("✖ expected {expected}, got no answer"),
+                    }
+                }
+                Check::MissingAnswer(answer) => {
+                    missing += 1;
+                    format!("? {answer} (no stored answer)")
+                }
+                Check::Failed(error) => {
+                    failures += 1;
+                    format!("✖ failed: {error}")
+                }
+            };
+            println!("Day {day} Part {part}: {line}");
+        }
+    }
+
+    if failures > 0 || missing > 0 {
+        return Err(Error::Mismatched {
+            parts: failures,
+            missing,
+        });
+    }
+
+    Ok(())
+}
+
+/// Stores the answers of the solved parts of `days`, see `verify --record`.
+/// Returns [`Error::Mismatched`] if any part failed, as its answer could not be recorded.
+fn record_answers(year: Year, days: &HashSet<Day>, records: &[PartRecord]) -> Result<(), Error> {
+    println!("\n{ANSI_BOLD}Recording answers{ANSI_RESET}");
+
+    let mut failures = 0;
+    let mut recorded = 0;
+
+    for day in all_days(year).filter(|day| days.contains(day)) {
+        let puzzle = PuzzleId::new(year, day);
+        let stored = match Answers::read_from_file(puzzle) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("Day {day}: failed to read stored answers: {e}");
+                failures += 1;
+                continue;
+            }
+        };
+
+        let records: Vec<&PartRecord> = records.iter().filter(|r| r.day == day).collect();
+
+        for record in records.iter().filter(|r| r.part == 1 || r.part == 2) {
+            if let Some(error) = &record.error {
+                failures += 1;
+                println!("Day {day} Part {}: ✖ failed: {error}", record.part);
+            }
+        }
+
+        let answers = record_day(&stored, &records);
+        if answers == stored {
+            continue;
+        }
+
+        if let Err(e) = answers.store_file(puzzle) {
+            eprintln!("Day {day}: failed to store answers: {e}");
+            failures += 1;
+            continue;
+        }
+
+        for part in 1..=2 {
+            if let Some(answer) = answers.get(part).filter(|a| stored.get(part) != Some(*a)) {
+                recorded += 1;
+                println!("Day {day} Part {part}: {answer}");
+            }
+        }
+    }
+
+    if recorded == 0 {
+        println!("The stored answers are up to date.");
+    }
+
+    if failures > 0 {
+        return Err(Error::Mismatched {
+            parts: failures,
+            missing: 0,
+        });
+    }
+
+    Ok(())
+}
+
+/// The stored answers, with the answers of the solved parts in `records` replacing them.
+fn record_day(stored: &Answers, records: &[&PartRecord]) -> Answers {
+    let mut answers = stored.clone();
+
+    for record in records.iter().filter(|r| r.is_solved()) {
+        if let Some(answer) = &record.answer {
+            answers.set(record.part, answer);
+        }
+    }
+
+    answers
+}
+
+fn check_day(answers: &Answers, records: &[&PartRecord]) -> Vec<(u8, Check)> {
+    (1..=2)
+        .filter_map(|part| {
+            if let Some(error) = records
+                .iter()
+                .find(|r| r.part == part)
+                .and_then(|r| r.error.clone())
+            {
+                return Some((part, Check::Failed(error)));
+            }
+
+            let actual = records
+                .iter()
+                .find(|r| r.part == part && r.is_solved())
+                .and_then(|r| r.answer.clone());
+
+            let check = match (answers.get(part), actual) {
+                (Some(expected), Some(actual)) if expected == actual => Check::Match(actual),
+                (Some(expected), actual) => Check::Mismatch {
+                    expected: expected.into(),
+                    actual,
+                },
+                (None, Some(actual)) => Check::MissingAnswer(actual),
+                (None, None) => return None,
+            };
+
+            Some((part, check))
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_day, record_day, Check};
+    use crate::day;
+    use crate::template::answers::Answers;
+    use crate::template::protocol::PartRecord;
+    use crate::template::timings::PartTiming;
+
+    fn record(part: u8, answer: Option<&str>) -> PartRecord {
+        PartRecord {
+            day: day!(1),
+            part,
+            answer: answer.map(Into::into),
+            timing: PartTiming::new(1_f64),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn checks_parts_against_answers() {
+        let answers = Answers {
+            part_1: Some("11".into()),
+            part_2: None,
+        };
+        let (p1, p2) = (record(1, Some("11")), record(2, Some("31")));
+        assert_eq!(
+            check_day(&answers, &[&p1, &p2]),
+            vec![
+                (1, Check::Match("11".into())),
+                (2, Check::MissingAnswer("31".into()))
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_mismatches_and_unsolved_parts() {
+        let answers = Answers {
+            part_1: Some("11".into()),
+            part_2: Some("31".into()),
+        };
+        let p1 = record(1, Some("12"));
+        assert_eq!(
+            check_day(&answers, &[&p1]),
+            vec![
+                (
+                    1,
+                    Check::Mismatch {
+                        expected: "11".into(),
+                        actual: Some("12".into())
+                    }
+                ),
+                (
+                    2,
+                    Check::Mismatch {
+                        expected: "31".into(),
+                        actual: None
+                    }
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn records_solved_parts() {
+        let stored = Answers {
+            part_1: Some("11".into()),
+            part_2: Some("31".into()),
+        };
+        let p1 = record(1, Some("12"));
+        let mut p2 = record(2, None);
+        p2.error = Some("invalid digit found in string".into());
+
+        // part two failed and keeps its stored answer.
+        assert_eq!(
+            record_day(&stored, &[&p1, &p2]),
+            Answers {
+                part_1: Some("12".into()),
+                part_2: Some("31".into()),
+            }
+        );
+        assert_eq!(record_day(&Answers::default(), &[]), Answers::default());
+    }
+
+    #[test]
+    fn reports_failed_parts() {
+        let answers = Answers {
+            part_1: Some("11".into()),
+            part_2: None,
+        };
+        let mut p1 = record(1, None);
+        p1.error = Some("invalid digit found in string".into());
+        assert_eq!(
+            check_day(&answers, &[&p1]),
+            vec![(1, Check::Failed("invalid digit found in string".into()))]
+        );
+    }
+}
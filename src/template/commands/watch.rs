@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+use std::{fs, thread};
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::{child_commands, Measurement};
+use crate::template::{data_dir, Error, Limits, PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// Changes are only acted on once no file changed for this long, so that bursts of saves
+/// (e.g. formatting on save) only trigger a single run.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Re-runs a day whenever its source, the shared library code or its input and example change.
+pub fn handle(puzzle: PuzzleId, is_release: bool) -> Result<(), Error> {
+    if !Path::new(&puzzle.bin_path()).exists() {
+        return Err(Error::Missing(format!(
+            "Day {} does not exist. Did you scaffold it?",
+            puzzle.day
+        )));
+    }
+
+    println!(
+        "Watching day {} for changes. Press Ctrl-C to stop.\n",
+        puzzle.day
+    );
+
+    let mut snapshot = take_snapshot(puzzle);
+    let mut answers = run_day(puzzle, is_release, &Answers::default());
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let mut current = take_snapshot(puzzle);
+        if current == snapshot {
+            continue;
+        }
+
+        loop {
+            thread::sleep(DEBOUNCE);
+            let next = take_snapshot(puzzle);
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+
+        snapshot = current;
+        println!();
+        answers = run_day(puzzle, is_release, &answers);
+    }
+}
+
+/// Modification times of all files that affect the day.
+fn take_snapshot(puzzle: PuzzleId) -> Snapshot {
+    let mut paths = vec![
+        PathBuf::from(puzzle.bin_path()),
+        PathBuf::from(puzzle.data_path("inputs", "txt")),
+        PathBuf::from(puzzle.data_path("examples", "txt")),
+        // the example of part two, see `read_file_part`.
+        PathBuf::from(format!(
+            "{}/examples/{}-2.txt",
+            data_dir(puzzle.year),
+            puzzle.day
+        )),
+    ];
+    collect_shared_sources(Path::new("src"), &mut paths);
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/// All files below `src/`, except for the other days' solutions.
+fn collect_shared_sources(dir: &Path, paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        if path.is_dir() {
+            if path != Path::new("src/bin") {
+                collect_shared_sources(&path, paths);
+            }
+        } else {
+            paths.push(path);
+        }
+    }
+}
+
+/// Builds the day, runs its tests and the real input, and prints a summary compared with
+/// the `previous` answers. Returns the new answers.
+fn run_day(puzzle: PuzzleId, is_release: bool, previous: &Answers) -> Answers {
+    let bin_name = puzzle.bin_name();
+    println!("{ANSI_BOLD}Day {}{ANSI_RESET}", puzzle.day);
+    println!("------");
+
+    let mut build_args = vec!["build", "--quiet", "--bin", &bin_name];
+    if is_release {
+        build_args.push("--release");
+    }
+
+    if !run_cargo(puzzle, &build_args) {
+        println!("Build: ✖ failed");
+        return previous.clone();
+    }
+
+    let tests_passed = run_cargo(puzzle, &["test", "--quiet", "--bin", &bin_name]);
+
+    let run =
+        child_commands::run_solution(puzzle, Measurement::Run, is_release, &Limits::default());
+    let records = match run {
+        Ok(run) => run.records,
+        Err(e) => {
+            eprintln!("Failed to run solution: {e}");
+            vec![]
+        }
+    };
+
+    let mut answers = Answers::default();
+    for record in records.iter().filter(|r| r.is_solved()) {
+        if let Some(answer) = &record.answer {
+            answers.set(record.part, answer);
+        }
+    }
+
+    println!("------");
+    let tests = if tests_passed {
+        "✔ passed"
+    } else {
+        "✖ failed"
+    };
+    println!("Tests: {tests}");
+    for line in diff_answers(previous, &answers) {
+        println!("{line}");
+    }
+
+    answers
+}
+
+fn run_cargo(puzzle: PuzzleId, args: &[&str]) -> bool {
+    // the tests read the examples of the puzzle's year.
+    Command::new("cargo")
+        .args(args)
+        .env("AOC_YEAR", puzzle.year.to_string())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// One line per part, comparing the answers with those of the last run.
+fn diff_answers(previous: &Answers, current: &Answers) -> Vec<String> {
+    (1..=2)
+        .map(|part| {
+            let change = match (previous.get(part), current.get(part)) {
+                (_, None) => "✖ no answer".to_string(),
+                (None, Some(answer)) => answer.to_string(),
+                (Some(old), Some(answer)) if old == answer => format!("{answer} (unchanged)"),
+                (Some(old), Some(answer)) => format!("{answer} (was {old})"),
+            };
+            format!("Part {part}: {change}")
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::diff_answers;
+    use crate::template::answers::Answers;
+
+    #[test]
+    fn diffs_answers_with_last_run() {
+        let previous = Answers {
+            part_1: Some("11".into()),
+            part_2: Some("30".into()),
+        };
+        let current = Answers {
+            part_1: Some("11".into()),
+            part_2: Some("31".into()),
+        };
+        assert_eq!(
+            diff_answers(&previous, &current),
+            vec!["Part 1: 11 (unchanged)", "Part 2: 31 (was 30)"]
+        );
+        assert_eq!(
+            diff_answers(&Answers::default(), &previous),
+            vec!["Part 1: 11", "Part 2: 30"]
+        );
+        assert_eq!(
+            diff_answers(&previous, &Answers::default()),
+            vec!["Part 1: ✖ no answer", "Part 2: ✖ no answer"]
+        );
+    }
+}
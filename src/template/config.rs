@@ -0,0 +1,320 @@
+/// Settings of the template, read from `aoc.toml` in the repository root.
+/// Every key is optional, missing keys fall back to the defaults below.
+use std::{collections::BTreeMap, fs, io, sync::OnceLock, time::Duration};
+
+use toml::{Table, Value};
+
+use crate::template::{Error, Year, MAX_DAY};
+
+const CONFIG_PATH: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Result<Config, String>> = OnceLock::new();
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// The year commands default to. `AOC_YEAR` and `--year` take precedence.
+    pub year: Option<Year>,
+    /// The directory that holds the data of all years.
+    pub data_dir: String,
+    /// The number of puzzle days of events that differ from the default, see [`Year::last_day`].
+    pub days: BTreeMap<Year, u8>,
+    pub bench: BenchConfig,
+    pub readme: ReadmeConfig,
+    pub submit: SubmitConfig,
+    pub scaffold: ScaffoldConfig,
+}
+
+/// How long `cargo time` benches each part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchConfig {
+    /// Approximate time spent on the samples of a part.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+/// Where `cargo time --store` writes the benchmark table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReadmeConfig {
+    pub path: String,
+    /// Text of the comments that enclose the table, e.g. `<!--- benchmarking table --->`.
+    pub marker: String,
+}
+
+/// What `cargo solve --submit` is allowed to send.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubmitConfig {
+    pub enabled: bool,
+    /// Refuse answers that were already rejected or are outside of known bounds.
+    pub refuse_known_wrong: bool,
+    /// Refuse answers like `0` instead of only warning about them.
+    pub refuse_suspicious: bool,
+}
+
+/// Which template `cargo scaffold` starts new days from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaffoldConfig {
+    /// The template used without `--template`.
+    pub template: String,
+    /// Project-local templates, e.g. `templates/grid.txt`. These override the built-in ones.
+    pub templates_dir: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            data_dir: "data".into(),
+            days: BTreeMap::new(),
+            bench: BenchConfig {
+                budget: Duration::from_secs(1),
+                min_samples: 10,
+                max_samples: 10000,
+            },
+            readme: ReadmeConfig {
+                path: "README.md".into(),
+                marker: "benchmarking table".into(),
+            },
+            submit: SubmitConfig {
+                enabled: true,
+                refuse_known_wrong: true,
+                refuse_suspicious: false,
+            },
+            scaffold: ScaffoldConfig {
+                template: "default".into(),
+                templates_dir: "templates".into(),
+            },
+        }
+    }
+}
+
+/// The config of this repository, loaded on first use.
+/// Returns [`Error::Parse`] if `aoc.toml` can not be read or is invalid.
+pub fn load() -> Result<&'static Config, Error> {
+    CONFIG
+        .get_or_init(Config::read_from_file)
+        .as_ref()
+        .map_err(|e| Error::Parse(format!("invalid {CONFIG_PATH}: {e}")))
+}
+
+/// The config of this repository. Falls back to the defaults if `aoc.toml` is invalid,
+/// binaries call [`load`] first to report that.
+pub fn get() -> &'static Config {
+    static DEFAULT: OnceLock<Config> = OnceLock::new();
+    load().unwrap_or_else(|_| DEFAULT.get_or_init(Config::default))
+}
+
+impl Config {
+    /// Reads `aoc.toml`. If not present, returns the default config.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(CONFIG_PATH) {
+            Ok(s) => Config::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let table: Table = s
+            .parse()
+            .map_err(|e: toml::de::Error| e.message().to_string())?;
+        let mut config = Config::default();
+
+        for (key, value) in &table {
+            match key.as_str() {
+                "year" => {
+                    let year = integer(key, value)?;
+                    config.year = Some(
+                        u16::try_from(year)
+                            .ok()
+                            .and_then(Year::new)
+                            .ok_or("expected `year` to be 2015 or later.")?,
+                    );
+                }
+                "data_dir" => config.data_dir = string(key, value)?,
+                "days" => {
+                    for (key, value) in section(key, value)? {
+                        let year = key
+                            .parse::<Year>()
+                            .map_err(|e| format!("invalid year `days.{key}`: {e}."))?;
+                        let days = u8::try_from(unsigned(key, value)?)
+                            .ok()
+                            .filter(|days| (1..=MAX_DAY).contains(days))
+                            .ok_or(format!(
+                                "expected `days.{key}` to be between 1 and {MAX_DAY}."
+                            ))?;
+                        config.days.insert(year, days);
+                    }
+                }
+                "bench" => {
+                    for (key, value) in section(key, value)? {
+                        match key.as_str() {
+                            "budget_ms" => {
+                                config.bench.budget = Duration::from_millis(unsigned(key, value)?);
+                            }
+                            "min_samples" => {
+                                config.bench.min_samples = unsigned(key, value)?.into();
+                            }
+                            "max_samples" => {
+                                config.bench.max_samples = unsigned(key, value)?.into();
+                            }
+                            _ => return Err(unknown_key("bench.", key)),
+                        }
+                    }
+                }
+                "readme" => {
+                    for (key, value) in section(key, value)? {
+                        match key.as_str() {
+                            "path" => config.readme.path = string(key, value)?,
+                            "marker" => config.readme.marker = string(key, value)?,
+                            _ => return Err(unknown_key("readme.", key)),
+                        }
+                    }
+                }
+                "submit" => {
+                    for (key, value) in section(key, value)? {
+                        match key.as_str() {
+                            "enabled" => config.submit.enabled = boolean(key, value)?,
+                            "refuse_known_wrong" => {
+                                config.submit.refuse_known_wrong = boolean(key, value)?;
+                            }
+                            "refuse_suspicious" => {
+                                config.submit.refuse_suspicious = boolean(key, value)?;
+                            }
+                            _ => return Err(unknown_key("submit.", key)),
+                        }
+                    }
+                }
+                "scaffold" => {
+                    for (key, value) in section(key, value)? {
+                        match key.as_str() {
+                            "template" => config.scaffold.template = string(key, value)?,
+                            "templates_dir" => config.scaffold.templates_dir = string(key, value)?,
+                            _ => return Err(unknown_key("scaffold.", key)),
+                        }
+                    }
+                }
+                _ => return Err(unknown_key("", key)),
+            }
+        }
+
+        if config.bench.min_samples == 0 || config.bench.min_samples > config.bench.max_samples {
+            return Err(
+                "expected `bench.min_samples` to be between 1 and `bench.max_samples`.".into(),
+            );
+        }
+
+        Ok(config)
+    }
+}
+
+fn unknown_key(prefix: &str, key: &str) -> String {
+    format!("unknown key `{prefix}{key}`.")
+}
+
+fn section<'a>(key: &str, value: &'a Value) -> Result<&'a Table, String> {
+    value
+        .as_table()
+        .ok_or(format!("expected `{key}` to be a table."))
+}
+
+fn string(key: &str, value: &Value) -> Result<String, String> {
+    value
+        .as_str()
+        .map(Into::into)
+        .ok_or(format!("expected `{key}` to be a string."))
+}
+
+fn boolean(key: &str, value: &Value) -> Result<bool, String> {
+    value
+        .as_bool()
+        .ok_or(format!("expected `{key}` to be a boolean."))
+}
+
+fn integer(key: &str, value: &Value) -> Result<i64, String> {
+    value
+        .as_integer()
+        .ok_or(format!("expected `{key}` to be an integer."))
+}
+
+fn unsigned(key: &str, value: &Value) -> Result<u64, String> {
+    u64::try_from(integer(key, value)?).map_err(|_| format!("expected `{key}` to be positive."))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Config;
+    use crate::template::Year;
+
+    #[test]
+    fn defaults_missing_keys() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn parses_config() {
+        let config = Config::parse(
+            r#"
+year = 2023
+data_dir = "puzzles"
+
+[bench]
+budget_ms = 250
+min_samples = 5
+max_samples = 500
+
+[readme]
+path = "docs/README.md"
+marker = "timings"
+
+[days]
+2026 = 10
+
+[submit]
+enabled = false
+refuse_suspicious = true
+
+[scaffold]
+template = "grid"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Year::new(2023));
+        assert_eq!(config.data_dir, "puzzles");
+        assert_eq!(config.days.get(&Year::new(2026).unwrap()), Some(&10));
+        assert_eq!(config.bench.budget, Duration::from_millis(250));
+        assert_eq!(config.bench.min_samples, 5);
+        assert_eq!(config.bench.max_samples, 500);
+        assert_eq!(config.readme.path, "docs/README.md");
+        assert_eq!(config.readme.marker, "timings");
+        assert!(!config.submit.enabled);
+        assert!(config.submit.refuse_known_wrong);
+        assert!(config.submit.refuse_suspicious);
+        assert_eq!(config.scaffold.template, "grid");
+        assert_eq!(config.scaffold.templates_dir, "templates");
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert_eq!(
+            Config::parse("[bench]\nbudget = 1").unwrap_err(),
+            "unknown key `bench.budget`."
+        );
+        assert_eq!(
+            Config::parse("year = \"2024\"").unwrap_err(),
+            "expected `year` to be an integer."
+        );
+        assert!(Config::parse("year = 2000").is_err());
+        assert_eq!(
+            Config::parse("[days]\n2025 = 26").unwrap_err(),
+            "expected `days.2025` to be between 1 and 25."
+        );
+        assert!(Config::parse("[bench]\nmin_samples = 20\nmax_samples = 10").is_err());
+        assert!(Config::parse("year = ").is_err());
+    }
+}
@@ -5,10 +5,16 @@ use std::str::FromStr;
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
+use crate::template::Year;
+
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
+/// The last day of the longest events. Events can have fewer days, see [`Year::last_day`].
+pub const MAX_DAY: u8 = 25;
+
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// Use [`Day::new_in_year`] to also check that a year has a puzzle on that day.
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -25,12 +31,28 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > MAX_DAY {
             return None;
         }
         Some(Self(day))
     }
 
+    /// Creates a [`Day`] from the provided value if `year` has a puzzle on that day,
+    /// returns [`None`] otherwise.
+    pub fn new_in_year(day: u8, year: Year) -> Option<Self> {
+        Self::new(day).filter(|day| day.0 <= year.last_day())
+    }
+
+    /// Parses a [`Day`] and checks that `year` has a puzzle on that day.
+    pub fn parse_in_year(s: &str, year: Year) -> Result<Self, DayFromStrError> {
+        let error = DayFromStrError {
+            last_day: year.last_day(),
+            year: Some(year),
+        };
+        let day = s.parse().map_err(|_| error)?;
+        Self::new_in_year(day, year).ok_or(error)
+    }
+
     // Not part of the public API
     #[doc(hidden)]
     pub const fn __new_unchecked(day: u8) -> Self {
@@ -45,12 +67,12 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
-    pub fn today() -> Option<Self> {
+    /// Returns the current day if it's a puzzle day of `year` in december, `None` otherwise.
+    pub fn today(year: Year) -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
+        if today.month() == 12 {
+            Self::new_in_year(u8::try_from(today.day()).ok()?, year)
         } else {
             None
         }
@@ -81,39 +103,54 @@ impl FromStr for Day {
     type Err = DayFromStrError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let day = s.parse().map_err(|_| DayFromStrError)?;
-        Self::new(day).ok_or(DayFromStrError)
+        let error = DayFromStrError {
+            last_day: MAX_DAY,
+            year: None,
+        };
+        let day = s.parse().map_err(|_| error)?;
+        Self::new(day).ok_or(error)
     }
 }
 
 /// An error which can be returned when parsing a [`Day`].
-#[derive(Debug)]
-pub struct DayFromStrError;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayFromStrError {
+    last_day: u8,
+    /// Set if the day was checked against the days of a year.
+    year: Option<Year>,
+}
 
 impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {}", self.last_day)?;
+        match self.year {
+            Some(year) => write!(f, " for {year}"),
+            None => Ok(()),
+        }
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// An iterator that yields every puzzle day of `year`, starting at the 1st.
+pub fn all_days(year: Year) -> AllDays {
+    AllDays::new(year)
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every puzzle day of a year, starting at the 1st.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(year: Year) -> Self {
+        Self {
+            current: 1,
+            last: year.last_day(),
+        }
     }
 }
 
@@ -121,10 +158,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and the last day of a year is never above 25.
         let day = Day(self.current);
         self.current += 1;
 
@@ -135,11 +172,12 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+/// Only checks the range of the longest events, as the year is not known at compile time.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::template::MAX_DAY,
             concat!(
                 "invalid day number `",
                 $day,
@@ -154,11 +192,15 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use std::collections::BTreeMap;
+
+    use super::{all_days, Day, DayFromStrError};
+    use crate::template::config::Config;
+    use crate::template::Year;
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = all_days(Year::new(2024).unwrap());
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +229,33 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn limits_days_to_the_event() {
+        let year = Year::new(2025).unwrap();
+        let config = Config::default();
+        assert_eq!(year.last_day_in(&config), 12);
+        assert_eq!(Year::new(2024).unwrap().last_day_in(&config), 25);
+
+        let config = Config {
+            days: BTreeMap::from([(year, 10)]),
+            ..Config::default()
+        };
+        assert_eq!(year.last_day_in(&config), 10);
+
+        let error = DayFromStrError {
+            last_day: 12,
+            year: Some(year),
+        };
+        assert_eq!(
+            error.to_string(),
+            "expecting a day number between 1 and 12 for 2025"
+        );
+        assert_eq!(
+            "26".parse::<Day>().unwrap_err().to_string(),
+            "expecting a day number between 1 and 25"
+        );
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,183 @@
+/// Errors of the template commands and solution binaries.
+/// Commands return these instead of exiting, the binaries decide how to report them.
+use std::{fmt::Display, io, process::ExitStatus};
+
+use crate::template::aoc_cli::AocCommandError;
+#[cfg(feature = "client")]
+use crate::template::aoc_client;
+
+#[derive(Debug)]
+pub enum Error {
+    /// aoc-cli is not installed or could not download, read or submit a puzzle.
+    AocCli(AocCommandError),
+    /// The built-in client could not download, read or submit a puzzle.
+    #[cfg(feature = "client")]
+    Client(aoc_client::Error),
+    IO(io::Error),
+    /// A file could not be accessed, e.g. while scaffolding a day.
+    File {
+        path: String,
+        source: io::Error,
+    },
+    /// The input of a solution could not be read.
+    Input(io::Error),
+    /// A stored file could not be read or parsed, e.g. `timings-history.json`.
+    Parse(String),
+    /// Building the solution binaries failed.
+    BuildFailed,
+    /// A child process, e.g. `cargo run`, exited with a non-zero status.
+    Child {
+        command: String,
+        status: ExitStatus,
+    },
+    /// The records written by a solution binary could not be read.
+    Protocol(String),
+    /// The arguments can not be combined or are not supported by this build.
+    Usage(String),
+    /// Something the command needs does not exist yet, e.g. stored timings.
+    Missing(String),
+    /// An answer was not submitted, see `[submit]` in `aoc.toml`.
+    Refused(String),
+    /// Parts of `cargo time --compare` regressed by more than `threshold` percent.
+    Regressed {
+        parts: usize,
+        threshold: f64,
+    },
+    /// Parts of `cargo verify` did not match their stored answer, or have no stored answer.
+    Mismatched {
+        parts: usize,
+        missing: usize,
+    },
+    /// The variants of a part did not give the same answer, see `cargo solve --variants`.
+    VariantsDiffer {
+        part: u8,
+    },
+    /// Parts of `cargo lint-solutions` gave the same answer regardless of their input.
+    InputIgnored {
+        parts: usize,
+    },
+}
+
+impl Error {
+    /// The exit code of a binary that failed with this error.
+    /// Invalid arguments exit with `2`, every other error with `1`.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::AocCli(AocCommandError::CommandNotFound) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            Error::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            #[cfg(feature = "client")]
+            Error::Client(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::File { path, source } => write!(f, "\"{path}\": {source}"),
+            Error::Input(e) => write!(f, "could not read input: {e}"),
+            Error::Parse(e) | Error::Usage(e) | Error::Missing(e) | Error::Refused(e) => {
+                write!(f, "{e}")
+            }
+            Error::BuildFailed => write!(f, "failed to build the solutions."),
+            Error::Child { command, status } => write!(f, "`{command}` exited with {status}."),
+            Error::Protocol(e) => write!(f, "could not read records of solution: {e}"),
+            Error::Regressed { parts, threshold } => {
+                write!(f, "{parts} part(s) regressed by more than {threshold}%.")
+            }
+            Error::Mismatched { parts, missing: 0 } => {
+                write!(f, "{parts} part(s) do not match their stored answer.")
+            }
+            Error::Mismatched { parts: 0, missing } => {
+                write!(f, "{missing} part(s) have no stored answer.")
+            }
+            Error::Mismatched { parts, missing } => write!(
+                f,
+                "{parts} part(s) do not match their stored answer, {missing} part(s) have none."
+            ),
+            Error::VariantsDiffer { part } => {
+                write!(f, "the variants of part {part} give different answers.")
+            }
+            Error::InputIgnored { parts } => {
+                write!(f, "{parts} part(s) do not depend on their input.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        Error::AocCli(e)
+    }
+}
+
+#[cfg(feature = "client")]
+impl From<aoc_client::Error> for Error {
+    fn from(e: aoc_client::Error) -> Self {
+        Error::Client(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Error;
+    use crate::template::aoc_cli::AocCommandError;
+
+    #[test]
+    fn formats_errors() {
+        assert_eq!(
+            Error::Mismatched {
+                parts: 2,
+                missing: 0
+            }
+            .to_string(),
+            "2 part(s) do not match their stored answer."
+        );
+        assert_eq!(
+            Error::Mismatched {
+                parts: 0,
+                missing: 3
+            }
+            .to_string(),
+            "3 part(s) have no stored answer."
+        );
+        assert_eq!(
+            Error::Regressed {
+                parts: 1,
+                threshold: 10.0
+            }
+            .to_string(),
+            "1 part(s) regressed by more than 10%."
+        );
+        assert_eq!(
+            Error::InputIgnored { parts: 2 }.to_string(),
+            "2 part(s) do not depend on their input."
+        );
+        assert_eq!(
+            Error::from(AocCommandError::CommandNotCallable).to_string(),
+            "failed to call aoc-cli: aoc-cli could not be called."
+        );
+    }
+
+    #[test]
+    fn uses_exit_code_2_for_usage_errors() {
+        assert_eq!(Error::Usage("no command specified.".into()).exit_code(), 2);
+        assert_eq!(Error::BuildFailed.exit_code(), 1);
+    }
+}
@@ -1,25 +1,49 @@
 use std::{env, fs};
 
+pub mod answers;
 pub mod aoc_cli;
+#[cfg(feature = "client")]
+pub mod aoc_client;
 pub mod commands;
+pub mod config;
+pub mod protocol;
+pub mod puzzle_examples;
+pub mod registry;
+pub mod report;
 pub mod runner;
+pub mod submissions;
+pub mod timing_history;
+pub mod timings;
 
 pub use day::*;
+pub use error::Error;
+pub use params::PuzzleParams;
+pub use readme_benchmarks::StatColumn;
+pub use run_multi::{Execution, Limits};
+pub use year::*;
 
 mod day;
+mod error;
+mod params;
 mod readme_benchmarks;
 mod run_multi;
-mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of the current year to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
+    read_puzzle_file(folder, PuzzleId::new(Year::current(), day))
+}
+
+/// Helper function that reads a text file to a string, e.g. like `data/2024/inputs/01.txt`.
+#[must_use]
+pub fn read_puzzle_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -29,7 +53,7 @@ pub fn read_file(folder: &str, day: Day) -> String {
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir(Year::current()))
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
@@ -37,32 +61,167 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Also creates the constant `SOLUTION`, which registers the parts with [`template::registry`].
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// With `parse = <function>`, the input is parsed once and both parts receive a reference to the
+/// parsed input, e.g. `solution!(12, parse = parse_grid)` for `fn parse_grid(&str) -> Grid` and
+/// `fn part_one(&Grid) -> Option<u32>`. The time of the parse function is reported separately.
+///
+/// With `params = <type>`, the parts receive the [`PuzzleParams`] of their input as a second
+/// argument, e.g. `solution!(14, params = Params)` for `fn part_one(&str, &Params)`.
+///
+/// Parts can have several implementations, e.g. `solution!(9, part_one = [naive, fast],
+/// part_two = [part_two])`. The first one runs by default, `--variants` benches all of them.
+/// `parse` and `params` go before the parts.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, parse = $parse:expr)? $(, params = $params:ty)?) => {
+        $crate::solution!(@impl $day, [$($parse)?], [$($params)?], [1; part_one] [2; part_two]);
+    };
+    ($day:expr $(, parse = $parse:expr)? $(, params = $params:ty)?, 1) => {
+        $crate::solution!(@impl $day, [$($parse)?], [$($params)?], [1; part_one]);
+    };
+    ($day:expr $(, parse = $parse:expr)? $(, params = $params:ty)?, 2) => {
+        $crate::solution!(@impl $day, [$($parse)?], [$($params)?], [2; part_two]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (
+        $day:expr $(, parse = $parse:expr)? $(, params = $params:ty)?,
+        part_one = [$one:expr $(, $ones:expr)*]
+        $(, part_two = [$two:expr $(, $twos:expr)*])?
+    ) => {
+        $crate::solution!(
+            @impl $day, [$($parse)?], [$($params)?],
+            [1; $one $(, $ones)*] $([2; $two $(, $twos)*])?
+        );
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (
+        $day:expr $(, parse = $parse:expr)? $(, params = $params:ty)?,
+        part_two = [$two:expr $(, $twos:expr)*]
+    ) => {
+        $crate::solution!(@impl $day, [$($parse)?], [$($params)?], [2; $two $(, $twos)*]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $parse:tt, $params:tt, $( [$part:expr; $func:expr $(, $variant:expr)*] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// The parts of this day, see `template::registry`.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                day: DAY,
+                parts: &[$($part),*],
+                is_parsed: $crate::solution!(@is_parsed $parse),
+                run: |input, part, options| {
+                    use $crate::template::runner::*;
+                    $crate::solution!(@parse_phase $parse, part, input, options);
+                    #[allow(unused_variables)]
+                    let params = $crate::solution!(@params $params, &options.input);
+                    $( if part == $part {
+                        // the parsed input can not be kept between runs, so every part parses it
+                        // again. `run_part` only times the part itself.
+                        let parsed = $crate::solution!(@parse $parse, input);
+                        return run_part(
+                            |input| $crate::solution!(@call $params, $func, input, params),
+                            $crate::solution!(@borrow $parse, parsed),
+                            DAY,
+                            $part,
+                            options,
+                        )
+                        .map(Some);
+                    } )*
+                    Ok(None)
+                },
+                solve: |input, part| {
+                    use $crate::template::runner::*;
+                    #[allow(unused_variables)]
+                    let params = $crate::solution!(@params $params, &InputSource::Puzzle);
+                    $( if part == $part {
+                        let parsed = $crate::solution!(@parse $parse, input);
+                        let answer = $crate::solution!(
+                            @call $params, $func, $crate::solution!(@borrow $parse, parsed), params
+                        );
+                        return answer
+                            .into_answer()
+                            .ok()
+                            .flatten()
+                            .map(|answer| answer.to_string());
+                    } )*
+                    None
+                },
+            };
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let result = RunOptions::from_args().and_then(|options| {
+                let input = options.read_input(DAY)?;
+                let parsed = $crate::solution!(@run_parse $parse, input.as_str(), options);
+                let input = $crate::solution!(@borrow $parse, parsed);
+                #[allow(unused_variables)]
+                let params = $crate::solution!(@params $params, &options.input);
+                if options.is_variants {
+                    $( if options.runs_part($part) { run_variants($part, &[
+                        Variant::new(
+                            stringify!($func),
+                            |input| $crate::solution!(@call $params, $func, input, params),
+                            input,
+                        ),
+                        $( Variant::new(
+                            stringify!($variant),
+                            |input| $crate::solution!(@call $params, $variant, input, params),
+                            input,
+                        ), )*
+                    ])?; } )*
+                } else {
+                    $( if options.runs_part($part) {
+                        run_part(
+                            |input| $crate::solution!(@call $params, $func, input, params),
+                            input,
+                            DAY,
+                            $part,
+                            &options,
+                        )?;
+                    } )*
+                }
+                Ok(())
+            });
+            if let Err(e) = result {
+                eprintln!("Error: {e}");
+                std::process::exit(e.exit_code());
+            }
         }
     };
+
+    // helpers of `@impl` that depend on whether the day has a parse function or parameters.
+    (@is_parsed []) => { false };
+    (@is_parsed [$parse:expr]) => { true };
+
+    (@parse_phase [], $part:ident, $input:ident, $options:ident) => {};
+    (@parse_phase [$parse:expr], $part:ident, $input:ident, $options:ident) => {
+        if $part == $crate::template::protocol::PARSE_PART {
+            let (_, record) = $crate::template::runner::run_parse($parse, $input, DAY, $options);
+            return Ok(Some(record));
+        }
+    };
+
+    (@parse [], $input:expr) => { $input };
+    (@parse [$parse:expr], $input:expr) => { $parse($input) };
+
+    (@run_parse [], $input:expr, $options:ident) => { $input };
+    (@run_parse [$parse:expr], $input:expr, $options:ident) => {
+        $crate::template::runner::run_parse($parse, $input, DAY, &$options).0
+    };
+
+    (@borrow [], $parsed:ident) => { $parsed };
+    (@borrow [$parse:expr], $parsed:ident) => { &$parsed };
+
+    (@params [], $source:expr) => { () };
+    (@params [$params:ty], $source:expr) => {
+        <$params as $crate::template::PuzzleParams>::for_input($source)
+    };
+
+    (@call [], $func:expr, $input:expr, $params:ident) => { $func($input) };
+    (@call [$params_ty:ty], $func:expr, $input:expr, $params:ident) => { $func($input, &$params) };
 }
@@ -0,0 +1,55 @@
+use crate::template::runner::InputSource;
+
+/// Constants of a puzzle that differ between the examples and the real input, e.g. the size of
+/// the grid of day 14.
+///
+/// Pass the type to `solution!` with `params = <type>`. The parts then receive the values that
+/// match their input as a second argument, e.g. `fn part_one(input: &str, params: &Params)`.
+pub trait PuzzleParams: Sized {
+    /// The values of the puzzle input.
+    fn real() -> Self;
+
+    /// The values of the examples.
+    fn example() -> Self;
+
+    /// The values of the input a solution runs against. Only `--example` and `--example-file`
+    /// use [`Self::example`].
+    fn for_input(input: &InputSource) -> Self {
+        match input {
+            InputSource::Example | InputSource::ExampleFile(_) => Self::example(),
+            _ => Self::real(),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleParams;
+    use crate::template::runner::InputSource;
+
+    #[derive(Debug, PartialEq)]
+    struct Size(usize);
+
+    impl PuzzleParams for Size {
+        fn real() -> Self {
+            Size(70)
+        }
+
+        fn example() -> Self {
+            Size(6)
+        }
+    }
+
+    #[test]
+    fn picks_params_of_input() {
+        assert_eq!(Size::for_input(&InputSource::Example), Size(6));
+        assert_eq!(
+            Size::for_input(&InputSource::ExampleFile("18-2.txt".into())),
+            Size(6)
+        );
+        assert_eq!(Size::for_input(&InputSource::Puzzle), Size(70));
+        assert_eq!(Size::for_input(&InputSource::Stdin), Size(70));
+    }
+}
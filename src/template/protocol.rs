@@ -0,0 +1,177 @@
+/// Machine-readable protocol between solution binaries and the commands that run them.
+///
+/// When a solution binary is invoked with `--records <path>`, the runner appends one JSON
+/// document per line to `<path>` for every part it ran. This keeps the human-readable output
+/// free to change and makes answers that look like timings harmless.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{timings::PartTiming, Day};
+
+/// The command-line flag that tells a solution binary where to write its records.
+pub const RECORDS_FLAG: &str = "--records";
+
+/// The part of the records of the parse phase, for days with a parse function.
+pub const PARSE_PART: u8 = 0;
+
+/// The outcome of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub day: Day,
+    /// The part, or [`PARSE_PART`] for the parse phase.
+    pub part: u8,
+    /// `None` if the part did not produce an answer.
+    /// Always `None` for the parse phase.
+    pub answer: Option<String>,
+    pub timing: PartTiming,
+    /// Set if running the part failed.
+    pub error: Option<String>,
+}
+
+impl PartRecord {
+    /// Whether the part produced an answer without failing.
+    pub fn is_solved(&self) -> bool {
+        self.answer.is_some() && self.error.is_none()
+    }
+}
+
+/// Appends a record to the records file at `path`.
+pub fn write_record(path: &Path, record: &PartRecord) -> io::Result<()> {
+    let line = JsonValue::from(record)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Reads all records from the records file at `path`. A missing file yields no records.
+pub fn read_records(path: &Path) -> Result<Vec<PartRecord>, String> {
+    match fs::read_to_string(path) {
+        Ok(s) => parse_records(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Parses records from JSON lines, skipping blank lines.
+pub fn parse_records(s: &str) -> Result<Vec<PartRecord>, String> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let json = JsonValue::from_str(line).map_err(|_| "record is not valid JSON.")?;
+            PartRecord::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let optional = |s: &Option<String>| s.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), optional(&value.answer));
+        map.insert("timing".into(), JsonValue::from(&value.timing));
+        map.insert("error".into(), optional(&value.error));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .ok_or("Expected record.part to be a number.")?;
+
+        let optional = |key: &str| {
+            json.get(key)
+                .map(|v| if v.is_null() { None } else { v.get::<String>() })
+                .ok_or(format!("Expected record.{key} to be null or string."))
+        };
+
+        let answer = optional("answer")?.cloned();
+        let error = optional("error")?.cloned();
+
+        let timing = json
+            .get("timing")
+            .ok_or("Expected record.timing to be present.")
+            .and_then(PartTiming::try_from)
+            .map_err(|e| format!("record.timing: {e}"))?;
+
+        Ok(PartRecord {
+            day,
+            part,
+            answer,
+            timing,
+            error,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_records, PartRecord};
+    use crate::{day, template::timings::PartTiming};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_records() {
+        let record = PartRecord {
+            day: day!(3),
+            part: 2,
+            answer: Some("Part 1: @ ( ) ms (2s @ 5 samples)".into()),
+            timing: PartTiming::new(1500_f64),
+            error: None,
+        };
+        let line = JsonValue::from(&record).stringify().unwrap();
+        let records = parse_records(&format!("{line}\n\n{line}\n")).unwrap();
+        assert_eq!(records, vec![record.clone(), record]);
+    }
+
+    #[test]
+    fn parses_unsolved_records() {
+        let records = parse_records(
+            r#"{ "day": "01", "part": 1, "answer": null, "timing": { "mean_nanos": 10 }, "error": null }"#,
+        )
+        .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].answer, None);
+        assert_eq!(records[0].is_solved(), false);
+    }
+
+    #[test]
+    fn rejects_malformed_records() {
+        assert!(parse_records(r#"{ "day": "01", "part": 1 }"#).is_err());
+        assert!(parse_records("Part 1: 42 (1.0ms @ 10 samples)").is_err());
+    }
+}
@@ -0,0 +1,167 @@
+/// Heading that starts the second part of a puzzle description.
+const PART_TWO_HEADING: &str = "--- Part Two ---";
+
+const FENCE: &str = "```";
+
+/// The examples of a puzzle, extracted from its description, e.g. `data/2024/puzzles/01.md`.
+///
+/// Examples are the first code block of each part. The expected answer of a part is the last
+/// emphasised code in its text, e.g. `` `*11*` ``, which is how the puzzles highlight them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzleExamples {
+    /// The first code block of part one, with a trailing newline.
+    pub example: Option<String>,
+    /// The first code block of part two, if it differs from the example of part one.
+    pub example_part_two: Option<String>,
+    pub answer_part_one: Option<String>,
+    pub answer_part_two: Option<String>,
+}
+
+impl PuzzleExamples {
+    pub fn parse(markdown: &str) -> Self {
+        let (part_one, part_two) = match markdown.find(PART_TWO_HEADING) {
+            Some(index) => markdown.split_at(index),
+            None => (markdown, ""),
+        };
+
+        let example = first_code_block(part_one);
+        let example_part_two =
+            first_code_block(part_two).filter(|block| Some(block) != example.as_ref());
+
+        Self {
+            example,
+            example_part_two,
+            answer_part_one: last_emphasized_code(part_one),
+            answer_part_two: last_emphasized_code(part_two),
+        }
+    }
+
+    /// Whether nothing could be extracted.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The title of a puzzle, e.g. `Historian Hysteria` for `--- Day 1: Historian Hysteria ---`.
+pub fn puzzle_title(markdown: &str) -> Option<String> {
+    let heading = markdown.lines().find(|line| line.contains("--- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    let title = title.trim_end().trim_end_matches("---").trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+fn first_code_block(markdown: &str) -> Option<String> {
+    let mut lines = markdown
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with(FENCE));
+    lines.next()?;
+
+    let block: String = lines
+        .take_while(|line| !line.trim_start().starts_with(FENCE))
+        .map(|line| format!("{line}\n"))
+        .collect();
+
+    (!block.is_empty()).then_some(block)
+}
+
+/// The last inline code that is emphasised, written either as `` `*x*` `` or `` *`x`* ``.
+fn last_emphasized_code(markdown: &str) -> Option<String> {
+    let mut is_in_block = false;
+    let mut last = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with(FENCE) {
+            is_in_block = !is_in_block;
+            continue;
+        }
+        if is_in_block {
+            continue;
+        }
+
+        // code spans are at the odd indices.
+        let pieces: Vec<&str> = line.split('`').collect();
+        for i in (1..pieces.len() - 1).step_by(2) {
+            let code = pieces[i];
+            let answer = if code.len() > 2 && code.starts_with('*') && code.ends_with('*') {
+                &code[1..code.len() - 1]
+            } else if pieces[i - 1].ends_with('*') && pieces[i + 1].starts_with('*') {
+                code
+            } else {
+                continue;
+            };
+
+            if !answer.is_empty() {
+                last = Some(answer.to_string());
+            }
+        }
+    }
+
+    last
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{puzzle_title, PuzzleExamples};
+
+    const PUZZLE: &str = r"\--- Day 1: Historian Hysteria ---
+----------
+
+For example:
+
+```
+3   4
+4   3
+```
+
+Maybe the lists are only off by a small amount! In the example above, this is `2`.
+
+In the example above, this is `2 + 1 + 0` = `*11*`.
+
+\--- Part Two ---
+----------
+
+```
+3   4
+4   3
+```
+
+So, for these example lists, the similarity score is *`31`*.
+";
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        let examples = PuzzleExamples::parse(PUZZLE);
+        assert_eq!(examples.example.as_deref(), Some("3   4\n4   3\n"));
+        assert_eq!(examples.example_part_two, None);
+        assert_eq!(examples.answer_part_one.as_deref(), Some("11"));
+        assert_eq!(examples.answer_part_two.as_deref(), Some("31"));
+    }
+
+    #[test]
+    fn extracts_separate_example_of_part_two() {
+        let puzzle = PUZZLE.replace("```\n3   4\n4   3\n```\n\nSo", "```\n1\n```\n\nSo");
+        let examples = PuzzleExamples::parse(&puzzle);
+        assert_eq!(examples.example_part_two.as_deref(), Some("1\n"));
+    }
+
+    #[test]
+    fn handles_puzzles_without_part_two() {
+        let part_one = PUZZLE.split("\\--- Part Two").next().unwrap();
+        let examples = PuzzleExamples::parse(part_one);
+        assert!(examples.example.is_some());
+        assert_eq!(examples.answer_part_two, None);
+        assert!(PuzzleExamples::parse("No examples.").is_empty());
+    }
+
+    #[test]
+    fn extracts_titles() {
+        assert_eq!(puzzle_title(PUZZLE).as_deref(), Some("Historian Hysteria"));
+        assert_eq!(
+            puzzle_title("## --- Day 12: Garden Groups ---").as_deref(),
+            Some("Garden Groups")
+        );
+        assert_eq!(puzzle_title("No title."), None);
+    }
+}
@@ -1,11 +1,20 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fmt::Display, fs, io, str::FromStr};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::timings::{format_bytes, format_nanos, PartTiming, Timings};
+use crate::template::{config, PuzzleId, Year};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+/// Comment that encloses the table, e.g. `<!--- benchmarking table --->`.
+fn get_marker(text: &str) -> String {
+    format!("<!--- {text} --->")
+}
+
+/// Marker of the table for a single year, e.g. `<!--- benchmarking table 2024 --->`.
+/// Readmes that only track one year can keep using the plain marker.
+fn get_year_marker(text: &str, year: Year) -> String {
+    get_marker(&format!("{text} {year}"))
+}
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -20,18 +29,102 @@ impl From<std::io::Error> for Error {
     }
 }
 
+/// Optional statistics columns that can be added to the table for each part.
+/// The memory columns are only filled for parts that were profiled with `cargo time --memory`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatColumn {
+    Min,
+    Median,
+    P95,
+    StdDev,
+    Outliers,
+    PeakMemory,
+    TotalMemory,
+    Blocks,
+}
+
+impl StatColumn {
+    pub const ALL: [StatColumn; 8] = [
+        StatColumn::Min,
+        StatColumn::Median,
+        StatColumn::P95,
+        StatColumn::StdDev,
+        StatColumn::Outliers,
+        StatColumn::PeakMemory,
+        StatColumn::TotalMemory,
+        StatColumn::Blocks,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            StatColumn::Min => "min",
+            StatColumn::Median => "median",
+            StatColumn::P95 => "p95",
+            StatColumn::StdDev => "σ",
+            StatColumn::Outliers => "outliers",
+            StatColumn::PeakMemory => "peak mem",
+            StatColumn::TotalMemory => "total mem",
+            StatColumn::Blocks => "blocks",
+        }
+    }
+
+    /// Formats the statistic, if it was recorded for `timing`.
+    fn format(self, timing: &PartTiming) -> Option<String> {
+        let stats = timing.stats.as_ref();
+        let memory = timing.memory.as_ref();
+
+        match self {
+            StatColumn::Min => stats.map(|s| format_nanos(s.min_nanos)),
+            StatColumn::Median => stats.map(|s| format_nanos(s.median_nanos)),
+            StatColumn::P95 => stats.map(|s| format_nanos(s.p95_nanos)),
+            StatColumn::StdDev => stats.map(|s| format_nanos(s.std_dev_nanos)),
+            StatColumn::Outliers => stats.map(|s| s.outliers.to_string()),
+            StatColumn::PeakMemory => memory.map(|m| format_bytes(m.peak_bytes)),
+            StatColumn::TotalMemory => memory.map(|m| format_bytes(m.total_bytes)),
+            StatColumn::Blocks => memory.map(|m| m.total_blocks.to_string()),
+        }
+    }
+}
+
+impl FromStr for StatColumn {
+    type Err = StatColumnFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "min" => Ok(StatColumn::Min),
+            "median" => Ok(StatColumn::Median),
+            "p95" => Ok(StatColumn::P95),
+            "std-dev" => Ok(StatColumn::StdDev),
+            "outliers" => Ok(StatColumn::Outliers),
+            "peak-memory" => Ok(StatColumn::PeakMemory),
+            "total-memory" => Ok(StatColumn::TotalMemory),
+            "blocks" => Ok(StatColumn::Blocks),
+            _ => Err(StatColumnFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`StatColumn`].
+#[derive(Debug)]
+pub struct StatColumnFromStrError;
+
+impl std::error::Error for StatColumnFromStrError {}
+
+impl Display for StatColumnFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "expecting one of `min`, `median`, `p95`, `std-dev`, `outliers`, `peak-memory`, `total-memory`, `blocks`",
+        )
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,76 +145,155 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(
+    prefix: &str,
+    marker: &str,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    columns: &[StatColumn],
+) -> String {
+    let header = format!("{prefix} Benchmarks ({year})");
+
+    // only show the parse phase if a day has one.
+    let has_parse = timings.data.iter().any(|timing| timing.parse.is_some());
+
+    let mut header_cells = vec!["Day".to_string()];
+    if has_parse {
+        header_cells.push("Parse".into());
+    }
+    for part in 1..=2 {
+        header_cells.push(format!("Part {part}"));
+        header_cells.extend(columns.iter().map(|c| format!("P{part} {}", c.label())));
+    }
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| {} |", header_cells.join(" | ")),
+        format!("|{}", " :---: |".repeat(header_cells.len())),
     ];
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let path = PuzzleId::new(year, timing.day).bin_path();
+        let mut cells = vec![format!("[Day {}](./{path})", timing.day.into_inner())];
+        if has_parse {
+            cells.extend(format_part(timing.parse.as_ref(), &[]));
+        }
+        cells.extend(format_part(timing.part_1.as_ref(), columns));
+        cells.extend(format_part(timing.part_2.as_ref(), columns));
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Formats the mean and the requested statistics of a part as table cells.
+fn format_part(timing: Option<&PartTiming>, columns: &[StatColumn]) -> Vec<String> {
+    let mut cells = vec![timing.map_or_else(|| "-".into(), |t| format!("`{}`", t.mean()))];
+
+    cells.extend(columns.iter().map(|column| {
+        timing
+            .and_then(|t| column.format(t))
+            .map_or_else(|| "-".into(), |value| format!("`{value}`"))
+    }));
+
+    cells
+}
+
+fn update_content(
+    s: &mut String,
+    marker_text: &str,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    columns: &[StatColumn],
+) -> Result<(), Error> {
+    let year_marker = get_year_marker(marker_text, year);
+    let marker = if s.contains(&year_marker) {
+        year_marker
+    } else {
+        get_marker(marker_text)
+    };
+
+    let positions = locate_table(s, &marker)?;
+    let table = construct_table("##", &marker, year, timings, total_millis, columns);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+pub fn update(year: Year, timings: Timings, columns: &[StatColumn]) -> Result<(), Error> {
+    let config = &config::get().readme;
+    let mut readme = String::from_utf8_lossy(&fs::read(&config.path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+    update_content(
+        &mut readme,
+        &config.marker,
+        year,
+        timings,
+        total_millis,
+        columns,
+    )?;
+    fs::write(&config.path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{get_year_marker, update_content, Error, StatColumn};
+    use crate::{
+        day,
+        template::timings::{MemoryStats, PartTiming, SampleStats, Timing, Timings},
+        template::Year,
+    };
+
+    const MARKER_TEXT: &str = "benchmarking table";
+    const MARKER: &str = "<!--- benchmarking table --->";
+
+    fn get_year() -> Year {
+        Year::new(2024).unwrap()
+    }
+
+    fn update(
+        s: &mut String,
+        timings: Timings,
+        total_millis: f64,
+        columns: &[StatColumn],
+    ) -> Result<(), Error> {
+        update_content(s, MARKER_TEXT, get_year(), timings, total_millis, columns)
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1: Some(PartTiming::new(10e6)),
+                    part_2: Some(PartTiming::new(20e6)),
                     total_nanos: 3e+10,
+                    interruption: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(PartTiming::new(30e6)),
+                    part_2: Some(PartTiming::new(40e6)),
                     total_nanos: 7e+10,
+                    interruption: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    parse: None,
+                    part_1: Some(PartTiming::new(40e6)),
+                    part_2: Some(PartTiming::new(50e6)),
                     total_nanos: 9e+10,
+                    interruption: None,
                 },
             ],
         }
@@ -131,47 +303,52 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, get_mock_timings(), 190.0, &[]).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, get_mock_timings(), 190.0, &[]).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update(&mut s, get_mock_timings(), 190.0, &[]).unwrap();
+        assert_eq!(s.contains("## Benchmarks (2024)"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, get_mock_timings(), 190.0, &[]).unwrap();
+        update(&mut s, get_mock_timings(), 190.0, &[]).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        assert_eq!(
+            s.matches("## Benchmarks (2024)")
+                .collect::<Vec<&str>>()
+                .len(),
+            1
+        );
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, get_mock_timings(), 190.0, &[]).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "## Benchmarks (2024)",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/2024_01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2024_02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2024_04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +357,114 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn updates_table_of_matching_year() {
+        let year_2023 = get_year_marker(MARKER_TEXT, Year::new(2023).unwrap());
+        let year_2024 = get_year_marker(MARKER_TEXT, get_year());
+        let mut s = format!("{year_2023}{year_2023}\n{year_2024}{year_2024}");
+        update(&mut s, get_mock_timings(), 190.0, &[]).unwrap();
+        assert_eq!(s.matches("## Benchmarks (2024)").count(), 1);
+        assert_eq!(s.matches("## Benchmarks (2023)").count(), 0);
+        assert!(s.starts_with(&format!("{year_2023}{year_2023}\n{year_2024}\n")));
+    }
+
+    #[test]
+    fn format_benchmarks_with_stat_columns() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let timings = Timings {
+            data: vec![Timing {
+                day: day!(1),
+                parse: None,
+                part_1: Some(PartTiming {
+                    mean_nanos: 1.5e6,
+                    stats: Some(SampleStats {
+                        samples: 100,
+                        min_nanos: 1.2e6,
+                        median_nanos: 1.4e6,
+                        p95_nanos: 2e6,
+                        std_dev_nanos: 1.5e5,
+                        outliers: 3,
+                    }),
+                    memory: None,
+                }),
+                part_2: Some(PartTiming::new(20e6)),
+                total_nanos: 21.5e6,
+                interruption: None,
+            }],
+        };
+        update(
+            &mut s,
+            timings,
+            21.5,
+            &[StatColumn::Median, StatColumn::Outliers],
+        )
+        .unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks (2024)",
+            "",
+            "| Day | Part 1 | P1 median | P1 outliers | Part 2 | P2 median | P2 outliers |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/2024_01.rs) | `1.5ms` | `1.4ms` | `3` | `20.0ms` | - | - |",
+            "",
+            "**Total: 21.50ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory_columns() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let timings = Timings {
+            data: vec![Timing {
+                day: day!(1),
+                parse: None,
+                part_1: Some(PartTiming {
+                    memory: Some(MemoryStats {
+                        peak_bytes: 1536,
+                        total_bytes: 3 * 1024 * 1024,
+                        total_blocks: 42,
+                    }),
+                    ..PartTiming::new(1.5e6)
+                }),
+                part_2: Some(PartTiming {
+                    memory: Some(MemoryStats {
+                        peak_bytes: 512,
+                        total_bytes: 512,
+                        total_blocks: 1,
+                    }),
+                    ..PartTiming::new(20e6)
+                }),
+                total_nanos: 21.5e6,
+                interruption: None,
+            }],
+        };
+        update(
+            &mut s,
+            timings,
+            21.5,
+            &[StatColumn::PeakMemory, StatColumn::Blocks],
+        )
+        .unwrap();
+        assert!(s.contains(
+            "| Day | Part 1 | P1 peak mem | P1 blocks | Part 2 | P2 peak mem | P2 blocks |"
+        ));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/2024_01.rs) | `1.5ms` | `1.5 KiB` | `42` | `20.0ms` | `512 B` | `1` |"
+        ));
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse_column() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some(PartTiming::new(5e6));
+        update(&mut s, timings, 195.0, &[]).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/2024_01.rs) | - | `10.0ms` | `20.0ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2024_02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |"));
+    }
 }
@@ -0,0 +1,63 @@
+/// Registry of all solutions, so that they can be run in-process or called as library functions.
+///
+/// Every `solution!` invocation creates a `SOLUTION` for its day. When built with the `registry`
+/// feature, the build script compiles all `src/bin/YYYY_NN.rs` files into the library and
+/// collects them here, together with their year.
+use crate::template::protocol::{PartRecord, PARSE_PART};
+use crate::template::runner::RunOptions;
+use crate::template::{Day, Error, PuzzleId};
+
+/// The parts of a single day.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: Day,
+    /// The parts passed to `solution!`.
+    pub parts: &'static [u8],
+    /// Whether the day has a parse function, see `solution!`.
+    pub is_parsed: bool,
+    /// Runs a part like the solution binary would, printing its result.
+    /// Returns `None` if the day does not have this part.
+    ///
+    /// For days with a parse function, [`PARSE_PART`] runs only the parse function.
+    /// The parts then parse the input again, without timing it.
+    pub run: fn(&str, u8, &RunOptions) -> Result<Option<PartRecord>, Error>,
+    /// Calls a part without any output. Returns `None` if the day does not have this part
+    /// or the part did not produce an answer, e.g. because it failed.
+    pub solve: fn(&str, u8) -> Option<String>,
+}
+
+impl Solution {
+    /// The parts to run, preceded by [`PARSE_PART`] if the day has a parse function.
+    pub fn phases(&self) -> impl Iterator<Item = u8> {
+        let parse = self.is_parsed.then_some(PARSE_PART);
+        parse.into_iter().chain(self.parts.iter().copied())
+    }
+}
+
+#[cfg(feature = "registry")]
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+/// Whether solutions were compiled into the library.
+pub const IS_ENABLED: bool = cfg!(feature = "registry");
+
+/// All registered solutions and their years, ordered by year and day.
+/// Empty without the `registry` feature.
+#[must_use]
+pub fn all() -> &'static [(u16, Solution)] {
+    #[cfg(feature = "registry")]
+    return generated::SOLUTIONS;
+
+    #[cfg(not(feature = "registry"))]
+    return &[];
+}
+
+/// The registered solution of a puzzle, if any.
+#[must_use]
+pub fn find(puzzle: PuzzleId) -> Option<&'static Solution> {
+    all()
+        .iter()
+        .find(|(year, solution)| *year == puzzle.year.into_inner() && solution.day == puzzle.day)
+        .map(|(_, solution)| solution)
+}
@@ -0,0 +1,442 @@
+/// Renders stored timings as a self-contained HTML page and an SVG badge.
+/// All charts are inline SVG, the page does not load any external assets.
+use std::fmt::Write;
+
+use crate::template::timing_history::TimingHistory;
+use crate::template::timings::{format_nanos, PartTiming, Timing, Timings};
+use crate::template::Year;
+
+const PART_1_COLOR: &str = "#9db1c5";
+const PART_2_COLOR: &str = "#ffd866";
+
+const LABEL_WIDTH: f64 = 64.0;
+const CHART_WIDTH: f64 = 560.0;
+const VALUE_WIDTH: f64 = 96.0;
+const ROW_HEIGHT: f64 = 22.0;
+const BAR_HEIGHT: f64 = 16.0;
+
+const LOG_CHART_HEIGHT: f64 = 220.0;
+/// Keeps the label of the topmost grid line inside the chart.
+const LOG_CHART_PADDING: f64 = 8.0;
+const LOG_BAR_WIDTH: f64 = 10.0;
+const LOG_GROUP_GAP: f64 = 8.0;
+
+const HISTORY_WIDTH: f64 = 240.0;
+const HISTORY_HEIGHT: f64 = 48.0;
+
+const STYLE: &str = "body { font-family: -apple-system, BlinkMacSystemFont, sans-serif; margin: 2rem auto; max-width: 760px; color: #222; }
+svg { display: block; margin: 1rem 0; }
+svg text { font-size: 12px; fill: #444; }
+table { border-collapse: collapse; }
+th, td { padding: 0.25rem 0.75rem; text-align: right; }
+th:first-child, td:first-child { text-align: left; }
+tr:nth-child(even) { background: #f4f4f4; }
+.legend span { display: inline-block; width: 0.8rem; height: 0.8rem; margin: 0 0.3rem 0 1rem; vertical-align: middle; }
+.history { display: inline-block; margin-right: 1.5rem; }";
+
+/// Renders the benchmark report of `year`.
+pub fn render_html(year: Year, timings: &Timings, history: &TimingHistory) -> String {
+    let mut html = String::new();
+    let total_millis = timings.total_millis();
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Advent of Code {year} benchmarks</title>
+<style>
+{STYLE}
+</style>
+</head>
+<body>
+<h1>Advent of Code {year} benchmarks</h1>
+<p><strong>Total: {total_millis:.2}ms</strong> over {} days.</p>
+<p class=\"legend\"><span style=\"background: {PART_1_COLOR}\"></span>Part 1<span style=\"background: {PART_2_COLOR}\"></span>Part 2</p>
+<h2>Runtime per day</h2>
+{}
+<h2>Part 1 and part 2 (log scale)</h2>
+{}
+<h2>Totals</h2>
+{}
+",
+        timings.data.len(),
+        day_chart(timings),
+        log_chart(timings),
+        totals_table(timings),
+    );
+
+    let history_charts = history_charts(timings, history);
+    if !history_charts.is_empty() {
+        let _ = write!(html, "<h2>History</h2>\n{history_charts}");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Renders a badge like the ones of shields.io, e.g. `AoC 2024 | 12.34ms`.
+pub fn render_badge(label: &str, value: &str) -> String {
+    let label_width = text_width(label);
+    let value_width = text_width(value);
+    let width = label_width + value_width;
+    let (label, value) = (escape(label), escape(value));
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"20\" role=\"img\" aria-label=\"{label}: {value}\">
+<title>{label}: {value}</title>
+<rect width=\"{label_width}\" height=\"20\" rx=\"3\" fill=\"#555\"/>
+<rect x=\"{label_width}\" width=\"{value_width}\" height=\"20\" rx=\"3\" fill=\"#4c1\"/>
+<g fill=\"#fff\" text-anchor=\"middle\" font-family=\"Verdana,Geneva,DejaVu Sans,sans-serif\" font-size=\"11\">
+<text x=\"{}\" y=\"14\">{label}</text>
+<text x=\"{}\" y=\"14\">{value}</text>
+</g>
+</svg>
+",
+        label_width / 2,
+        label_width + value_width / 2,
+    )
+}
+
+/// Approximate width of a badge text in Verdana 11px, including padding.
+fn text_width(s: &str) -> usize {
+    s.chars().count() * 7 + 12
+}
+
+/// Horizontal bars with the runtime of both parts of every day stacked.
+fn day_chart(timings: &Timings) -> String {
+    let max = timings
+        .data
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold(0_f64, f64::max);
+    let scale = if max > 0_f64 {
+        CHART_WIDTH / max
+    } else {
+        0_f64
+    };
+
+    #[allow(clippy::cast_precision_loss)]
+    let height = timings.data.len() as f64 * ROW_HEIGHT;
+    let mut svg = svg_open(LABEL_WIDTH + CHART_WIDTH + VALUE_WIDTH, height);
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = i as f64 * ROW_HEIGHT;
+        let _ = write!(
+            svg,
+            "<text x=\"0\" y=\"{:.1}\">Day {}</text>",
+            y + 15.0,
+            timing.day
+        );
+
+        let mut x = LABEL_WIDTH;
+        for (part, color) in [(1, PART_1_COLOR), (2, PART_2_COLOR)] {
+            let Some(part_timing) = part_of(timing, part) else {
+                continue;
+            };
+            let width = part_timing.mean_nanos * scale;
+            let _ = write!(
+                svg,
+                "<rect x=\"{x:.1}\" y=\"{:.1}\" width=\"{width:.1}\" height=\"{BAR_HEIGHT}\" fill=\"{color}\"><title>Day {} Part {part}: {}</title></rect>",
+                y + 3.0,
+                timing.day,
+                part_timing.mean()
+            );
+            x += width;
+        }
+
+        let _ = write!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\">{}</text>",
+            x + 6.0,
+            y + 15.0,
+            day_total(timing)
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Vertical bars of both parts of every day on a logarithmic scale.
+fn log_chart(timings: &Timings) -> String {
+    let values: Vec<f64> = timings
+        .data
+        .iter()
+        .flat_map(|t| [part_of(t, 1), part_of(t, 2)])
+        .flatten()
+        .map(|p| p.mean_nanos)
+        .filter(|nanos| *nanos > 0_f64)
+        .collect();
+
+    let (Some(min), Some(max)) = (
+        values.iter().copied().reduce(f64::min),
+        values.iter().copied().reduce(f64::max),
+    ) else {
+        return "<p>No parts solved yet.</p>".into();
+    };
+
+    // leave room below the fastest part, so that its bar does not end up empty.
+    let low = (min.log10() - 0.5).floor();
+    let high = max.log10().ceil().max(low + 1_f64);
+    let bottom = LOG_CHART_PADDING + LOG_CHART_HEIGHT;
+    let y_of = |nanos: f64| bottom - (nanos.log10() - low) / (high - low) * LOG_CHART_HEIGHT;
+
+    let group_width = 2_f64 * LOG_BAR_WIDTH + LOG_GROUP_GAP;
+    #[allow(clippy::cast_precision_loss)]
+    let width = LABEL_WIDTH + timings.data.len() as f64 * group_width;
+    let mut svg = svg_open(width, bottom + ROW_HEIGHT);
+
+    // one grid line per power of ten.
+    #[allow(clippy::cast_possible_truncation)]
+    for exponent in low as i32..=high as i32 {
+        let nanos = 10_f64.powi(exponent);
+        let y = y_of(nanos);
+        let _ = write!(
+            svg,
+            "<line x1=\"{LABEL_WIDTH}\" y1=\"{y:.1}\" x2=\"{width:.1}\" y2=\"{y:.1}\" stroke=\"#ddd\"/><text x=\"0\" y=\"{:.1}\">{}</text>",
+            y + 4.0,
+            format_nanos(nanos)
+        );
+    }
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let x = LABEL_WIDTH + i as f64 * group_width + LOG_GROUP_GAP / 2_f64;
+
+        for (part, color) in [(1, PART_1_COLOR), (2, PART_2_COLOR)] {
+            let Some(part_timing) = part_of(timing, part).filter(|p| p.mean_nanos > 0_f64) else {
+                continue;
+            };
+            let y = y_of(part_timing.mean_nanos);
+            let _ = write!(
+                svg,
+                "<rect x=\"{:.1}\" y=\"{y:.1}\" width=\"{LOG_BAR_WIDTH}\" height=\"{:.1}\" fill=\"{color}\"><title>Day {} Part {part}: {}</title></rect>",
+                x + f64::from(part - 1) * LOG_BAR_WIDTH,
+                bottom - y,
+                timing.day,
+                part_timing.mean()
+            );
+        }
+
+        let _ = write!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>",
+            x + LOG_BAR_WIDTH,
+            bottom + 16.0,
+            timing.day.into_inner()
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn totals_table(timings: &Timings) -> String {
+    let mut html = String::from(
+        "<table>\n<tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Total</th></tr>\n",
+    );
+
+    for timing in &timings.data {
+        let part = |part| part_of(timing, part).map_or_else(|| "-".into(), PartTiming::mean);
+        let _ = writeln!(
+            html,
+            "<tr><td>Day {}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            timing.day,
+            part(1),
+            part(2),
+            day_total(timing)
+        );
+    }
+
+    let _ = writeln!(
+        html,
+        "<tr><th>Total</th><th></th><th></th><th>{:.2}ms</th></tr>\n</table>",
+        timings.total_millis()
+    );
+    html
+}
+
+/// A line chart of the total runtime of every day across the stored runs.
+/// Days that were only stored once are skipped.
+fn history_charts(timings: &Timings, history: &TimingHistory) -> String {
+    let mut html = String::new();
+
+    for timing in &timings.data {
+        let runs: Vec<(String, f64)> = history
+            .for_day(timing.day)
+            .filter_map(|entry| {
+                let day = entry.timings.iter().find(|t| t.day == timing.day)?;
+                let label = format!("{} {}", entry.date(), entry.commit.as_deref().unwrap_or(""));
+                Some((label, day.total_nanos))
+            })
+            .collect();
+
+        if runs.len() < 2 {
+            continue;
+        }
+
+        let max = runs.iter().map(|(_, nanos)| *nanos).fold(0_f64, f64::max);
+        #[allow(clippy::cast_precision_loss)]
+        let step = HISTORY_WIDTH / (runs.len() - 1) as f64;
+        let point = |i: usize, nanos: f64| {
+            #[allow(clippy::cast_precision_loss)]
+            let x = i as f64 * step;
+            let y = if max > 0_f64 {
+                HISTORY_HEIGHT - nanos / max * (HISTORY_HEIGHT - 4.0) - 2.0
+            } else {
+                HISTORY_HEIGHT / 2.0
+            };
+            (x, y)
+        };
+
+        let points: Vec<String> = runs
+            .iter()
+            .enumerate()
+            .map(|(i, (_, nanos))| {
+                let (x, y) = point(i, *nanos);
+                format!("{x:.1},{y:.1}")
+            })
+            .collect();
+
+        let _ = write!(
+            html,
+            "<div class=\"history\"><strong>Day {}</strong> {} → {}\n{}<polyline points=\"{}\" fill=\"none\" stroke=\"#4c7\" stroke-width=\"2\"/>",
+            timing.day,
+            format_nanos(runs[0].1),
+            format_nanos(runs[runs.len() - 1].1),
+            svg_open(HISTORY_WIDTH, HISTORY_HEIGHT),
+            points.join(" ")
+        );
+
+        for (i, (label, nanos)) in runs.iter().enumerate() {
+            let (x, y) = point(i, *nanos);
+            let _ = write!(
+                html,
+                "<circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"3\" fill=\"#4c7\"><title>{}: {}</title></circle>",
+                escape(label.trim()),
+                format_nanos(*nanos)
+            );
+        }
+
+        html.push_str("</svg></div>\n");
+    }
+
+    html
+}
+
+fn svg_open(width: f64, height: f64) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.0}\" height=\"{height:.0}\" viewBox=\"0 0 {width:.0} {height:.0}\">"
+    )
+}
+
+fn part_of(timing: &Timing, part: u8) -> Option<&PartTiming> {
+    match part {
+        1 => timing.part_1.as_ref(),
+        2 => timing.part_2.as_ref(),
+        _ => None,
+    }
+}
+
+/// The total runtime of a day, or why it did not finish.
+fn day_total(timing: &Timing) -> String {
+    match timing.interruption {
+        Some(interruption) => interruption.to_string(),
+        None => format_nanos(timing.total_nanos),
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render_badge, render_html};
+    use crate::day;
+    use crate::template::timing_history::{HistoryEntry, TimingHistory};
+    use crate::template::timings::{Interruption, PartTiming, Timing, Timings};
+    use crate::template::Year;
+
+    fn timing(day: crate::template::Day, part_1: f64, part_2: Option<f64>) -> Timing {
+        Timing {
+            day,
+            parse: None,
+            part_1: Some(PartTiming::new(part_1)),
+            part_2: part_2.map(PartTiming::new),
+            total_nanos: part_1 + part_2.unwrap_or_default(),
+            interruption: None,
+        }
+    }
+
+    fn entry(timings: Vec<Timing>) -> HistoryEntry {
+        HistoryEntry {
+            commit: Some("a1b2c3d".into()),
+            stored_at: 1_733_011_200,
+            machine: "laptop".into(),
+            profile: "release".into(),
+            timings,
+        }
+    }
+
+    #[test]
+    fn renders_report() {
+        let mut timed_out = timing(day!(2), 5e6, None);
+        timed_out.interruption = Some(Interruption::TimedOut);
+
+        let timings = Timings {
+            data: vec![timing(day!(1), 1e3, Some(2e6)), timed_out],
+        };
+        let html = render_html(
+            Year::new(2024).unwrap(),
+            &timings,
+            &TimingHistory::default(),
+        );
+
+        assert!(html.contains("<title>Advent of Code 2024 benchmarks</title>"));
+        assert!(html.contains("Total: 7.00ms"));
+        assert!(html.contains("<tr><td>Day 01</td><td>1.0µs</td><td>2.0ms</td><td>2.0ms</td></tr>"));
+        assert!(html.contains("<tr><td>Day 02</td><td>5.0ms</td><td>-</td><td>timed out</td></tr>"));
+        // one grid line per power of ten between 100ns and 10ms.
+        assert_eq!(html.matches("<line ").count(), 6);
+        assert!(!html.contains("<h2>History</h2>"));
+        // the page must not load anything.
+        assert!(!html.contains("src=") && !html.contains("href="));
+    }
+
+    #[test]
+    fn renders_history() {
+        let timings = Timings {
+            data: vec![timing(day!(1), 2e6, None), timing(day!(2), 1e6, None)],
+        };
+        let history = TimingHistory {
+            entries: vec![
+                entry(vec![timing(day!(1), 4e6, None), timing(day!(2), 1e6, None)]),
+                entry(vec![timing(day!(1), 2e6, None)]),
+            ],
+        };
+        let html = render_html(Year::new(2024).unwrap(), &timings, &history);
+
+        assert!(html.contains("<h2>History</h2>"));
+        assert!(html.contains("<strong>Day 01</strong> 4.0ms → 2.0ms"));
+        // day 2 was only stored once.
+        assert!(!html.contains("<strong>Day 02</strong>"));
+        assert_eq!(html.matches("<polyline ").count(), 1);
+    }
+
+    #[test]
+    fn renders_badge() {
+        let badge = render_badge("AoC 2024", "12.34ms");
+        assert!(badge.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(badge.contains(">AoC 2024</text>"));
+        assert!(badge.contains(">12.34ms</text>"));
+    }
+}
@@ -1,194 +1,707 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet, VecDeque},
+    fs,
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Mutex},
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::protocol::PartRecord;
+use crate::template::registry::{self, Solution};
+use crate::template::runner::{InputSource, RunOptions};
+use crate::template::{Day, Error, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    timings::{Interruption, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Controls how the solution binaries of multiple days are invoked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Execution {
+    /// Invoke `cargo run` for one day after another, streaming its output.
+    Serial,
+    /// Build all binaries once up front, then run up to `jobs` of them concurrently.
+    /// Output is buffered per day and printed in day order.
+    Parallel { jobs: usize },
+    /// Call the solutions compiled into this binary, without invoking cargo.
+    /// Requires the `registry` feature.
+    InProcess,
+}
 
-    let mut need_space = false;
+impl Execution {
+    /// Parallel execution with `jobs` workers, if requested.
+    pub fn from_jobs(jobs: Option<usize>) -> Self {
+        jobs.map_or(Execution::Serial, |jobs| Execution::Parallel {
+            jobs: jobs.max(1),
+        })
+    }
+}
+
+/// What is measured while the solutions run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Measurement {
+    /// Run every part once.
+    Run,
+    /// Bench every part (`--time`).
+    Time,
+    /// Run every part once and profile its heap usage (`--memory`).
+    /// Solution binaries are built with the `dhat-heap` feature for this.
+    Memory,
+}
+
+impl Measurement {
+    /// Arguments that select this measurement in a solution binary.
+    fn to_args(self) -> &'static [&'static str] {
+        match self {
+            Measurement::Run => &[],
+            Measurement::Time => &["--time"],
+            Measurement::Memory => &["--memory"],
+        }
+    }
 
+    /// Cargo features the solution binaries need for this measurement.
+    fn cargo_args(self) -> &'static [&'static str] {
+        match self {
+            Measurement::Memory => &["--features", "dhat-heap"],
+            Measurement::Run | Measurement::Time => &[],
+        }
+    }
+}
+
+/// Limits for each solution binary. Timeouts are wall-clock time.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Limits {
+    /// Timeout for all parts of a day.
+    pub day_timeout: Option<Duration>,
+    /// Timeout for a single part, measured between the records the binary writes.
+    pub part_timeout: Option<Duration>,
+    /// Maximum address space in bytes (`RLIMIT_AS`, Linux only).
+    pub memory_bytes: Option<u64>,
+    /// Maximum CPU time in seconds (`RLIMIT_CPU`, Linux only).
+    pub cpu_seconds: Option<u64>,
+}
+
+impl Limits {
+    pub fn is_unlimited(&self) -> bool {
+        *self == Limits::default()
+    }
+}
+
+/// Result of running the solutions of multiple days.
+pub struct MultiRun {
+    /// Records of every part that ran, in day order.
+    pub records: Vec<PartRecord>,
+    /// Set if the solutions were benched.
+    pub timings: Option<Timings>,
+}
+
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    measurement: Measurement,
+    execution: Execution,
+    limits: Limits,
+) -> Result<MultiRun, Error> {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let days: Vec<Day> = all_days(year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        .collect();
+
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
+    let mut all_records: Vec<PartRecord> = vec![];
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let mut need_space = false;
+    let mut interrupted: Vec<(Day, Interruption)> = vec![];
+
+    let mut on_day_done = |day: Day, run: child_commands::SolutionRun| {
+        if let Some(interruption) = run.interruption {
+            println!("✖ {interruption}");
+            interrupted.push((day, interruption));
+        } else if run.records.is_empty() {
+            println!("Not solved.");
+        }
+
+        if !run.records.is_empty() || run.interruption.is_some() {
+            let mut timing = child_commands::timing_from_records(&run.records, day);
+            timing.interruption = run.interruption;
+            timings.push(timing);
+            all_records.extend(run.records);
+        }
+    };
+    let mut print_header = |day: Day| {
+        if need_space {
+            println!();
+        }
+        need_space = true;
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    match execution {
+        Execution::Serial => {
+            for day in days {
+                print_header(day);
+                let puzzle = PuzzleId::new(year, day);
+                let run = child_commands::run_solution(puzzle, measurement, is_release, &limits)?;
+                on_day_done(day, run);
+            }
+        }
+        Execution::Parallel { jobs } => {
+            child_commands::build_solutions(is_release, measurement)?;
+
+            run_parallel(
+                year,
+                days,
+                jobs,
+                measurement,
+                is_release,
+                limits,
+                |day, output| {
+                    print_header(day);
+                    print!("{}", output.stdout);
+                    eprint!("{}", output.stderr);
+                    on_day_done(day, output.run);
+                },
+            )?;
+        }
+        Execution::InProcess => {
+            if !registry::IS_ENABLED {
+                return Err(Error::Usage(
+                    "Running solutions in-process requires the `registry` feature.".into(),
+                ));
             }
-        });
 
-    if is_timed {
+            if !limits.is_unlimited() {
+                eprintln!("Limits are ignored when running solutions in-process.");
+            }
+
+            for day in days {
+                print_header(day);
+                let records = run_in_process(PuzzleId::new(year, day), measurement)?;
+                on_day_done(
+                    day,
+                    child_commands::SolutionRun {
+                        records,
+                        interruption: None,
+                    },
+                );
+            }
+        }
+    }
+
+    if !interrupted.is_empty() {
+        let days: Vec<String> = interrupted
+            .iter()
+            .map(|(day, interruption)| format!("Day {day} ({interruption})"))
+            .collect();
+        println!("\n{ANSI_BOLD}Interrupted:{ANSI_RESET} {}", days.join(", "));
+    }
+
+    let timings = (measurement == Measurement::Time).then(|| {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
-    }
+        timings
+    });
+
+    Ok(MultiRun {
+        records: all_records,
+        timings,
+    })
 }
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
+/// Runs prebuilt solution binaries on `jobs` worker threads.
+/// `on_output` is called on the current thread in the order of `days`.
+/// Stops at the first day that could not be run.
+fn run_parallel(
+    year: Year,
+    days: Vec<Day>,
+    jobs: usize,
+    measurement: Measurement,
+    is_release: bool,
+    limits: Limits,
+    mut on_output: impl FnMut(Day, child_commands::SolutionOutput),
+) -> Result<(), Error> {
+    let queue = Mutex::new(days.into_iter().enumerate().collect::<VecDeque<_>>());
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let tx = tx.clone();
+            let queue = &queue;
+
+            scope.spawn(move || loop {
+                let Some((index, day)) = queue.lock().unwrap().pop_front() else {
+                    break;
+                };
+
+                let puzzle = PuzzleId::new(year, day);
+                let output = child_commands::run_built_solution(
+                    puzzle,
+                    measurement,
+                    is_release,
+                    &InputSource::Puzzle,
+                    None,
+                    &limits,
+                );
+
+                if tx.send((index, day, output)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // drop the original sender so that the receiver stops once all workers are done.
+        drop(tx);
+
+        // buffer finished days until all days before them have been printed.
+        let mut finished = BTreeMap::new();
+        let mut next_index = 0;
+
+        for (index, day, output) in rx {
+            finished.insert(index, (day, output));
+
+            while let Some((day, output)) = finished.remove(&next_index) {
+                // dropping the receiver stops the workers after their current day.
+                on_output(day, output?);
+                next_index += 1;
+            }
+        }
+
+        Ok(())
+    })
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
+/// Runs a registered solution in the current process.
+/// Parts that panic are reported as not solved, the remaining parts still run.
+fn run_in_process(puzzle: PuzzleId, measurement: Measurement) -> Result<Vec<PartRecord>, Error> {
+    // skip days that have not been scaffolded yet.
+    let Some(solution) = registry::find(puzzle) else {
+        return Ok(vec![]);
+    };
+
+    let input_path = puzzle.data_path("inputs", "txt");
+    let Ok(input) = fs::read_to_string(&input_path) else {
+        eprintln!("Could not open input file \"{input_path}\".");
+        return Ok(vec![]);
+    };
+
+    run_phases(solution, &input, measurement)
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Runs the parse function and every part of `solution` against `input`.
+/// Parsing is timed as its own phase, the timings of the parts do not include it.
+fn run_phases(
+    solution: &Solution,
+    input: &str,
+    measurement: Measurement,
+) -> Result<Vec<PartRecord>, Error> {
+    let options = RunOptions {
+        is_timed: measurement == Measurement::Time,
+        is_memory: measurement == Measurement::Memory,
+        ..RunOptions::default()
+    };
+
+    let mut records = vec![];
+    for part in solution.phases() {
+        let run = panic::catch_unwind(AssertUnwindSafe(|| (solution.run)(input, part, &options)));
+        if let Ok(record) = run {
+            records.extend(record?);
+        }
+    }
+
+    Ok(records)
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading the records they emit.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Limits, Measurement};
+    use crate::template::protocol::{read_records, PartRecord, PARSE_PART, RECORDS_FLAG};
+    use crate::template::runner::InputSource;
+    use crate::template::timings::Interruption;
+    use crate::template::{Day, Error, PuzzleId};
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        env, fs,
+        io::{self, Read},
+        path::{Path, PathBuf},
+        process::{self, Child, Command, ExitStatus, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    /// How often a running solution is checked against its timeouts.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Records of a solution binary and whether it was stopped early.
+    pub struct SolutionRun {
+        pub records: Vec<PartRecord>,
+        pub interruption: Option<Interruption>,
+    }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    /// Buffered output of a solution binary.
+    pub struct SolutionOutput {
+        pub stdout: String,
+        pub stderr: String,
+        pub run: SolutionRun,
+    }
+
+    /// Build all solution binaries with a single cargo invocation.
+    pub fn build_solutions(is_release: bool, measurement: Measurement) -> Result<(), Error> {
+        cargo_build(&["--bins"], is_release, measurement)
+    }
+
+    fn cargo_build(
+        targets: &[&str],
+        is_release: bool,
+        measurement: Measurement,
+    ) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet"];
+        args.extend(targets);
 
         if is_release {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+        args.extend(measurement.cargo_args());
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
+        let status = Command::new("cargo")
             .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::BuildFailed)
+        }
+    }
+
+    /// Path of a solution binary built by [`build_solutions`].
+    fn get_path_for_built_bin(puzzle: PuzzleId, is_release: bool) -> PathBuf {
+        let target_dir = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into());
+        let profile = if is_release { "release" } else { "debug" };
+        let file_name = format!("{}{}", puzzle.bin_name(), env::consts::EXE_SUFFIX);
+        Path::new(&target_dir).join(profile).join(file_name)
+    }
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+    /// Command that runs `part`, or all parts, of a built solution binary against `input` within
+    /// `limits`. Standard input is empty, so [`InputSource::Stdin`] runs against an empty input.
+    fn built_solution_command(
+        puzzle: PuzzleId,
+        measurement: Measurement,
+        is_release: bool,
+        input: &InputSource,
+        part: Option<u8>,
+        records_path: &Path,
+        limits: &Limits,
+    ) -> Command {
+        let mut cmd = Command::new(get_path_for_built_bin(puzzle, is_release));
+        cmd.arg(RECORDS_FLAG)
+            .arg(records_path)
+            .env("AOC_YEAR", puzzle.year.to_string())
+            .stdin(Stdio::null())
+            .args(measurement.to_args())
+            .args(input.to_args());
+
+        if let Some(part) = part {
+            cmd.arg("--part").arg(part.to_string());
+        }
 
-        let mut output = vec![];
+        apply_resource_limits(&mut cmd, limits);
+        cmd
+    }
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+    /// Run a solution bin that was built by [`build_solutions`] and buffer its output.
+    pub fn run_built_solution(
+        puzzle: PuzzleId,
+        measurement: Measurement,
+        is_release: bool,
+        input: &InputSource,
+        part: Option<u8>,
+        limits: &Limits,
+    ) -> Result<SolutionOutput, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok(SolutionOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+                run: SolutionRun {
+                    records: vec![],
+                    interruption: None,
+                },
             });
+        }
+
+        let records_path = get_records_path(puzzle);
+        let _ = fs::remove_file(&records_path);
+
+        let child = built_solution_command(
+            puzzle,
+            measurement,
+            is_release,
+            input,
+            part,
+            &records_path,
+            limits,
+        )
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+
+        let output = child.and_then(|mut child| {
+            // drain the pipes while waiting, a full pipe would block the child.
+            let stdout = read_pipe(child.stdout.take());
+            let stderr = read_pipe(child.stderr.take());
+            let interruption = wait_with_limits(&mut child, &records_path, limits)?;
+            Ok((join_pipe(stdout), join_pipe(stderr), interruption))
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let records = read_records(&records_path).map_err(Error::Protocol);
+        let _ = fs::remove_file(&records_path);
+
+        let (stdout, stderr, interruption) = output?;
+        Ok(SolutionOutput {
+            stdout,
+            stderr,
+            run: SolutionRun {
+                records: records?,
+                interruption,
+            },
+        })
+    }
+
+    fn read_pipe(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+        thread::spawn(move || {
+            let mut buf = vec![];
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            String::from_utf8_lossy(&buf).into()
+        })
+    }
+
+    fn join_pipe(handle: thread::JoinHandle<String>) -> String {
+        handle.join().unwrap_or_default()
+    }
+
+    /// Run the solution bin for a given day and collect the records of the parts it ran.
+    /// Without limits, the bin is invoked via `cargo run`. With limits, it is built first
+    /// so that neither cargo nor the compiler count against them.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        measurement: Measurement,
+        is_release: bool,
+        limits: &Limits,
+    ) -> Result<SolutionRun, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok(SolutionRun {
+                records: vec![],
+                interruption: None,
+            });
+        }
+
+        let records_path = get_records_path(puzzle);
+        let records_path_str = records_path.to_string_lossy().to_string();
+        // a previous run might have been interrupted before cleaning up.
+        let _ = fs::remove_file(&records_path);
+
+        let bin_name = puzzle.bin_name();
+
+        let mut cmd = if limits.is_unlimited() {
+            let mut args = vec!["run", "--quiet", "--bin", &bin_name];
+
+            if is_release {
+                args.push("--release");
+            }
+
+            args.extend(measurement.cargo_args());
+            args.extend(["--", RECORDS_FLAG, &records_path_str]);
+            // mirror `--time` and `--memory` flags to child invocations.
+            args.extend(measurement.to_args());
+
+            let mut cmd = Command::new("cargo");
+            cmd.args(&args)
+                .env("AOC_YEAR", puzzle.year.to_string())
+                .stdin(Stdio::null());
+            cmd
+        } else {
+            cargo_build(&["--bin", &bin_name], is_release, measurement)?;
+            built_solution_command(
+                puzzle,
+                measurement,
+                is_release,
+                &InputSource::Puzzle,
+                None,
+                &records_path,
+                limits,
+            )
+        };
+
+        let interruption = cmd
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .and_then(|mut child| wait_with_limits(&mut child, &records_path, limits));
+
+        let records = read_records(&records_path).map_err(Error::Protocol);
+        let _ = fs::remove_file(&records_path);
+
+        Ok(SolutionRun {
+            interruption: interruption?,
+            records: records?,
+        })
+    }
+
+    /// Waits for a solution binary to exit, killing it once it exceeds a timeout.
+    fn wait_with_limits(
+        child: &mut Child,
+        records_path: &Path,
+        limits: &Limits,
+    ) -> io::Result<Option<Interruption>> {
+        if limits.day_timeout.is_none() && limits.part_timeout.is_none() {
+            return child.wait().map(interruption_of);
+        }
+
+        let started = Instant::now();
+        let mut part_started = started;
+        let mut records_written = 0;
+
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(interruption_of(status));
+            }
+
+            let now = Instant::now();
+
+            if limits.part_timeout.is_some() {
+                // every finished part appends a record.
+                let written = fs::read_to_string(records_path).map_or(0, |s| s.lines().count());
+                if written != records_written {
+                    records_written = written;
+                    part_started = now;
+                }
+            }
+
+            let is_day_timed_out = limits.day_timeout.is_some_and(|t| now - started > t);
+            let is_part_timed_out = limits.part_timeout.is_some_and(|t| now - part_started > t);
+
+            if is_day_timed_out || is_part_timed_out {
+                child.kill()?;
+                child.wait()?;
+                return Ok(Some(Interruption::TimedOut));
+            }
+
+            thread::sleep(POLL_INTERVAL);
         }
+    }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+    /// A binary that was terminated by a signal was killed, e.g. for exceeding a resource limit.
+    #[cfg(unix)]
+    fn interruption_of(status: ExitStatus) -> Option<Interruption> {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal().map(|_| Interruption::Killed)
+    }
 
-        Ok(output)
+    #[cfg(not(unix))]
+    fn interruption_of(_: ExitStatus) -> Option<Interruption> {
+        None
+    }
+
+    #[cfg(target_os = "linux")]
+    fn apply_resource_limits(cmd: &mut Command, limits: &Limits) {
+        use std::os::unix::process::CommandExt;
+
+        let Limits {
+            memory_bytes,
+            cpu_seconds,
+            ..
+        } = *limits;
+
+        if memory_bytes.is_none() && cpu_seconds.is_none() {
+            return;
+        }
+
+        // SAFETY: the closure only calls `setrlimit`, which is async-signal-safe.
+        unsafe {
+            cmd.pre_exec(move || {
+                let set = |resource, value: u64| {
+                    let limit = libc::rlimit {
+                        rlim_cur: value,
+                        rlim_max: value,
+                    };
+                    if libc::setrlimit(resource, &limit) == 0 {
+                        Ok(())
+                    } else {
+                        Err(io::Error::last_os_error())
+                    }
+                };
+
+                if let Some(bytes) = memory_bytes {
+                    set(libc::RLIMIT_AS, bytes)?;
+                }
+                if let Some(seconds) = cpu_seconds {
+                    set(libc::RLIMIT_CPU, seconds)?;
+                }
+                Ok(())
+            });
+        }
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    #[cfg(not(target_os = "linux"))]
+    fn apply_resource_limits(_: &mut Command, limits: &Limits) {
+        if limits.memory_bytes.is_some() || limits.cpu_seconds.is_some() {
+            eprintln!("Memory and CPU limits are only supported on Linux.");
+        }
+    }
+
+    fn get_records_path(puzzle: PuzzleId) -> PathBuf {
+        env::temp_dir().join(format!(
+            "aoc-records-{}-{}-{}.jsonl",
+            puzzle.year,
+            puzzle.day,
+            process::id()
+        ))
+    }
+
+    /// Collect the timings of all solved parts and of the parse phase.
+    pub fn timing_from_records(records: &[PartRecord], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            interruption: None,
         };
 
-        output
+        records
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            .filter(|record| record.is_solved() || record.part == PARSE_PART)
+            .for_each(|record| {
+                let timing = Some(record.timing.clone());
+
+                match record.part {
+                    PARSE_PART => timings.parse = timing,
+                    1 => timings.part_1 = timing,
+                    2 => timings.part_2 = timing,
+                    _ => return,
                 }
 
-                timings.total_nanos += nanos;
+                timings.total_nanos += record.timing.mean_nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,53 +718,117 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::timing_from_records;
 
         use crate::day;
+        use crate::template::protocol::{parse_records, PartRecord, PARSE_PART};
+        use crate::template::timings::PartTiming;
+
+        fn record(part: u8, answer: Option<&str>, mean_nanos: f64) -> PartRecord {
+            PartRecord {
+                day: day!(1),
+                part,
+                answer: answer.map(Into::into),
+                timing: PartTiming::new(mean_nanos),
+                error: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = timing_from_records(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    record(1, Some("0"), 74.13_f64),
+                    record(2, Some("10"), 74130000_f64),
                 ],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.part_1.unwrap().mean_nanos, 74.13_f64);
+            assert_approx_eq!(res.part_2.unwrap().mean_nanos, 74130000_f64);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
+        fn collects_with_patterns_in_answer() {
+            let records = parse_records(concat!(
+                r#"{ "day": "01", "part": 1, "answer": "@ @ @ ( ) ms (2s @ 5 samples)", "timing": { "mean_nanos": 2000000000 }, "error": null }"#,
+                "\n",
+                r#"{ "day": "01", "part": 2, "answer": "10s (100ms @ 1 samples)", "timing": { "mean_nanos": 100000000 }, "error": null }"#,
+            ))
+            .unwrap();
+            let res = timing_from_records(&records, day!(1));
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.part_1.unwrap().mean_nanos, 2000000000_f64);
+            assert_approx_eq!(res.part_2.unwrap().mean_nanos, 100000000_f64);
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn collects_missing_parts() {
+            let mut failed = record(2, Some("1"), 10_f64);
+            failed.error = Some("failed".into());
+
+            let res = timing_from_records(&[record(1, None, 10_f64), failed], day!(1));
+            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn collects_parse_times() {
+            let res = timing_from_records(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    record(PARSE_PART, None, 30_f64),
+                    record(1, Some("1"), 10_f64),
+                    record(2, Some("2"), 20_f64),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_approx_eq!(res.total_nanos, 60_f64);
+            assert_approx_eq!(res.parse.unwrap().mean_nanos, 30_f64);
+            assert_approx_eq!(res.part_1.unwrap().mean_nanos, 10_f64);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{run_phases, Measurement};
+    use crate::template::protocol::PARSE_PART;
+
+    /// A day whose parse function is much slower than its parts.
+    #[allow(dead_code)]
+    mod parsed_day {
+        use std::{thread, time::Duration};
+
+        crate::solution!(1, parse = parse);
+
+        pub const PARSE_MILLIS: u64 = 20;
+
+        pub fn parse(input: &str) -> Vec<u64> {
+            thread::sleep(Duration::from_millis(PARSE_MILLIS));
+            input.lines().map(|line| line.parse().unwrap()).collect()
         }
+
+        pub fn part_one(numbers: &[u64]) -> Option<u64> {
+            Some(numbers.iter().sum())
+        }
+
+        pub fn part_two(numbers: &[u64]) -> Option<u64> {
+            numbers.iter().max().copied()
+        }
+    }
+
+    #[test]
+    fn times_parsing_separately_in_process() {
+        let records = run_phases(&parsed_day::SOLUTION, "1\n2\n", Measurement::Run).unwrap();
+
+        let parts: Vec<u8> = records.iter().map(|r| r.part).collect();
+        assert_eq!(parts, [PARSE_PART, 1, 2]);
+        assert_eq!(records[1].answer.as_deref(), Some("3"));
+
+        let parse_nanos = (parsed_day::PARSE_MILLIS * 1_000_000) as f64;
+        assert!(records[0].timing.mean_nanos >= parse_nanos);
+        assert!(records[1].timing.mean_nanos < parse_nanos);
+        assert!(records[2].timing.mean_nanos < parse_nanos);
     }
 }
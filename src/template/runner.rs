@@ -1,104 +1,592 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
+use std::fs;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, stdout, Write};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env};
 
+use crate::template::answers;
+use crate::template::config;
+use crate::template::protocol::{self, PartRecord, PARSE_PART, RECORDS_FLAG};
+use crate::template::submissions::{self, Submission, Submissions, Verdict};
+use crate::template::timings::{format_bytes, MemoryStats, PartTiming, SampleStats};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, Error, PuzzleId, Year, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Summary statistics of the samples taken while running a part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+    /// Number of samples outside of 1.5 times the interquartile range.
+    pub outliers: usize,
+}
+
+impl BenchStats {
+    /// Computes statistics over a non-empty set of samples.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+        let len = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / len;
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+        let variance = if sorted.len() > 1 {
+            sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (len - 1.0)
+        } else {
+            0.0
+        };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let fence = 1.5 * (q3 - q1);
+        let outliers = sorted
+            .iter()
+            .filter(|&&x| x < q1 - fence || x > q3 + fence)
+            .count();
+
+        Self {
+            samples: samples.len() as u128,
+            mean: nanos_to_duration(mean),
+            min: nanos_to_duration(sorted[0]),
+            median: nanos_to_duration(percentile(&sorted, 0.5)),
+            p95: nanos_to_duration(percentile(&sorted, 0.95)),
+            std_dev: nanos_to_duration(variance.sqrt()),
+            outliers,
+        }
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
-        let input = input.clone();
+/// Linearly interpolated percentile `p` (0..=1) of a sorted, non-empty slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
 
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn nanos_to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+impl From<&BenchStats> for PartTiming {
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn from(stats: &BenchStats) -> Self {
+        Self {
+            mean_nanos: stats.mean.as_nanos() as f64,
+            stats: Some(SampleStats {
+                samples: stats.samples as u64,
+                min_nanos: stats.min.as_nanos() as f64,
+                median_nanos: stats.median.as_nanos() as f64,
+                p95_nanos: stats.p95.as_nanos() as f64,
+                std_dev_nanos: stats.std_dev.as_nanos() as f64,
+                outliers: stats.outliers as u64,
+            }),
+            memory: None,
+        }
+    }
+}
+
+/// Where a solution reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input, e.g. `data/2024/inputs/01.txt`.
+    #[default]
+    Puzzle,
+    /// The example, e.g. `data/2024/examples/01.txt` (`--example`).
+    Example,
+    /// An arbitrary file (`--input <path>`).
+    File(PathBuf),
+    /// An additional example, e.g. `data/2024/examples/01-2.txt` (`--example-file <path>`).
+    ExampleFile(PathBuf),
+    /// Standard input (`--input -`).
+    Stdin,
+}
+
+impl InputSource {
+    /// The source selected by the `--input <path>`, `--example-file <path>` and `--example` flags.
+    pub fn from_flags(
+        input: Option<String>,
+        example_file: Option<String>,
+        example: bool,
+    ) -> Result<Self, String> {
+        match (input, example_file, example) {
+            (Some(_), _, true) => Err("`--input` can not be combined with `--example`.".into()),
+            (Some(_), Some(_), _) | (None, Some(_), true) => {
+                Err("`--example-file` can not be combined with `--input` or `--example`.".into())
+            }
+            (Some(path), None, false) if path == "-" => Ok(InputSource::Stdin),
+            (Some(path), None, false) => Ok(InputSource::File(path.into())),
+            (None, Some(path), false) => Ok(InputSource::ExampleFile(path.into())),
+            (None, None, true) => Ok(InputSource::Example),
+            (None, None, false) => Ok(InputSource::Puzzle),
+        }
+    }
+
+    /// The flags that select this source, to pass on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Example => vec!["--example".into()],
+            InputSource::File(path) => vec!["--input".into(), path.to_string_lossy().into()],
+            InputSource::ExampleFile(path) => {
+                vec!["--example-file".into(), path.to_string_lossy().into()]
+            }
+            InputSource::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    pub fn read(&self, puzzle: PuzzleId) -> io::Result<String> {
+        match self {
+            InputSource::Puzzle => fs::read_to_string(puzzle.data_path("inputs", "txt")),
+            InputSource::Example => fs::read_to_string(puzzle.data_path("examples", "txt")),
+            InputSource::File(path) | InputSource::ExampleFile(path) => fs::read_to_string(path),
+            InputSource::Stdin => io::read_to_string(io::stdin()),
+        }
+    }
+}
+
+/// Controls how [`run_part`] runs a part. Solution binaries read these from their arguments.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunOptions {
+    /// Bench the part instead of running it once (`--time`).
+    pub is_timed: bool,
+    /// Profile the heap usage of the first run of the part (`--memory`).
+    /// Only has an effect in builds with the `dhat-heap` feature.
+    pub is_memory: bool,
+    /// Submit the answer of this part (`--submit <part>`).
+    pub submit_part: Option<u8>,
+    /// Append a record of every part to this file (`--records <path>`).
+    pub records_path: Option<PathBuf>,
+    /// Where to read the input from (`--input <path>`, `--input -`, `--example` or
+    /// `--example-file <path>`).
+    pub input: InputSource,
+    /// Bench every variant of a part instead of running it (`--variants`).
+    pub is_variants: bool,
+    /// Only run this part (`--part <part>`), e.g. so that a panic in part one can not stop part two.
+    pub only_part: Option<u8>,
+}
+
+impl RunOptions {
+    pub fn from_args() -> Result<Self, Error> {
+        config::load()?;
+        Self::parse(&env::args().collect::<Vec<_>>())
+    }
+
+    fn parse(args: &[String]) -> Result<Self, Error> {
+        let value_of = |flag: &str| args.iter().position(|x| x == flag).map(|i| args.get(i + 1));
+        let part_of = |flag: &str, format: &str| {
+            value_of(flag)
+                .map(|value| {
+                    value
+                        .and_then(|part| part.parse::<u8>().ok())
+                        .ok_or_else(|| {
+                            Error::Usage(format!("Unexpected command-line input. Format: {format}"))
+                        })
+                })
+                .transpose()
+        };
+
+        let submit_part = part_of("--submit", "cargo solve 1 --submit 1")?;
+        let only_part = part_of("--part", "--part 1")?;
+
+        let input = InputSource::from_flags(
+            value_of("--input").flatten().cloned(),
+            value_of("--example-file").flatten().cloned(),
+            args.iter().any(|x| x == "--example"),
+        )
+        .map_err(Error::Usage)?;
+
+        Ok(Self {
+            is_timed: args.iter().any(|x| x == "--time"),
+            is_memory: args.iter().any(|x| x == "--memory"),
+            submit_part,
+            records_path: value_of(RECORDS_FLAG).flatten().map(PathBuf::from),
+            input,
+            is_variants: args.iter().any(|x| x == "--variants"),
+            only_part,
+        })
+    }
+
+    /// Whether `part` runs, see [`Self::only_part`].
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.only_part.is_none_or(|only| only == part)
+    }
+
+    /// Reads the input of `day` of the current year from the selected source.
+    pub fn read_input(&self, day: Day) -> Result<String, Error> {
+        let puzzle = PuzzleId::new(Year::current(), day);
+        self.input.read(puzzle).map_err(Error::Input)
+    }
+}
+
+/// The return types a part can have: `Option<T>`, or `Result<T, E>` for parts that can fail.
+pub trait PartResult {
+    type Answer: Display;
+
+    /// The answer of the part, `None` if it has none yet, or the message of its error.
+    fn into_answer(self) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
 
-        func(input)
+    fn into_answer(self) -> Result<Option<T>, String> {
+        Ok(self)
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
+
+    /// Errors are formatted with `{:#}`, which includes the source chain of e.g. `anyhow` errors.
+    fn into_answer(self) -> Result<Option<T>, String> {
+        self.map(Some).map_err(|e| format!("{e:#}"))
+    }
+}
+
+/// Runs the parse function of a day, so that its time is reported separately from the parts.
+/// Returns the parsed input and the record of the parse phase.
+pub fn run_parse<'a, I: ?Sized, P>(
+    func: impl Fn(&'a I) -> P,
+    input: &'a I,
+    day: Day,
+    options: &RunOptions,
+) -> (P, PartRecord) {
+    let (parsed, base_time, memory) = run_once(&func, input, options);
+
+    print!("Parse");
+    let stats = collect_stats(&func, input, &base_time, options.is_timed);
+    print!("\r");
+    println!("Parse:{}", format_stats(&stats, memory.as_ref()));
+
+    let record = PartRecord {
+        day,
+        part: PARSE_PART,
+        answer: None,
+        timing: PartTiming {
+            memory,
+            ..PartTiming::from(&stats)
+        },
+        error: None,
     };
-    let base_time = timer.elapsed();
 
-    hook(&result);
+    write_record(&record, options);
+
+    (parsed, record)
+}
+
+pub fn run_part<'a, I: ?Sized, R: PartResult>(
+    func: impl Fn(&'a I) -> R,
+    input: &'a I,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) -> Result<PartRecord, Error> {
+    let part_str = format!("Part {part}");
+
+    let (result, base_time, memory) = run_once(&func, input, options);
+    let result = result.into_answer();
+    print_result(&result, &part_str, "");
+
+    // failed parts are not benched.
+    let stats = collect_stats(&func, input, &base_time, options.is_timed && result.is_ok());
+
+    print_result(&result, &part_str, &format_stats(&stats, memory.as_ref()));
+
+    let (answer, error) = match &result {
+        Ok(answer) => (answer.as_ref().map(ToString::to_string), None),
+        Err(e) => (None, Some(e.clone())),
+    };
+
+    let record = PartRecord {
+        day,
+        part,
+        answer,
+        timing: PartTiming {
+            memory,
+            ..PartTiming::from(&stats)
+        },
+        error,
+    };
+
+    write_record(&record, options);
+
+    if let Ok(Some(result)) = result {
+        submit_result(result, day, part, options)?;
+    }
+
+    Ok(record)
+}
+
+/// The formatted answer of a part, or its error.
+type Answer = Result<Option<String>, String>;
+
+/// An implementation of a part that `--variants` compares against the other implementations.
+pub struct Variant<'a> {
+    pub name: &'static str,
+    run: Box<dyn Fn() -> (Answer, Duration) + 'a>,
+    bench: Box<dyn Fn(&Duration) -> BenchStats + 'a>,
+}
+
+impl<'a> Variant<'a> {
+    pub fn new<I: ?Sized, R: PartResult>(
+        name: &'static str,
+        func: impl Fn(&'a I) -> R + Copy + 'a,
+        input: &'a I,
+    ) -> Self {
+        Self {
+            name,
+            run: Box::new(move || {
+                let timer = Instant::now();
+                let result = func(input);
+                let elapsed = timer.elapsed();
+                let answer = result.into_answer();
+                (answer.map(|a| a.map(|a| a.to_string())), elapsed)
+            }),
+            bench: Box::new(move |base_time| bench(&func, input, base_time)),
+        }
+    }
+}
+
+/// Checks that all variants of a part give the same answer, then benches each of them
+/// and prints how they compare. Returns [`Error::VariantsDiffer`] if the answers differ.
+pub fn run_variants(part: u8, variants: &[Variant]) -> Result<(), Error> {
+    let runs: Vec<_> = variants.iter().map(|variant| (variant.run)()).collect();
+
+    let answer = &runs[0].0;
+    if runs.iter().any(|(result, _)| result != answer) {
+        println!("Part {part}: {ANSI_BOLD}variants differ{ANSI_RESET}");
+        for (variant, (result, _)) in variants.iter().zip(&runs) {
+            println!("  {}: {}", variant.name, format_answer(result));
+        }
+        return Err(Error::VariantsDiffer { part });
+    }
+
+    let mut rows = vec![];
+    for (variant, (_, base_time)) in variants.iter().zip(&runs) {
+        let label = format!("Part {part}: {}", variant.name);
+        print!("{label}");
+        let stats = (variant.bench)(base_time);
+        // overwrite the progress of the bench.
+        print!(
+            "\r{}\r",
+            " ".repeat(label.chars().count() + " > benching".len())
+        );
+        rows.push((variant.name, stats));
+    }
+
+    println!(
+        "Part {part}: {ANSI_BOLD}{}{ANSI_RESET}",
+        format_answer(answer)
+    );
+    for line in format_variants(&rows) {
+        println!("  {line}");
+    }
+
+    Ok(())
+}
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+fn format_answer(result: &Answer) -> String {
+    match result {
+        Ok(Some(answer)) if answer.contains('\n') => "▼".into(),
+        Ok(Some(answer)) => answer.clone(),
+        Ok(None) => "✖".into(),
+        Err(e) => format!("✖ failed: {e}"),
+    }
+}
+
+/// Formats one aligned line per variant, with its time relative to the fastest variant.
+fn format_variants(rows: &[(&str, BenchStats)]) -> Vec<String> {
+    // variants faster than the resolution of the clock take `0ns`. They count as `1ns` when
+    // comparing, so that the ratio stays finite.
+    const MIN_DURATION: Duration = Duration::from_nanos(1);
+
+    let fastest = rows
+        .iter()
+        .map(|(_, stats)| stats.mean)
+        .min()
+        .unwrap_or_default();
+
+    let cells: Vec<[String; 4]> = rows
+        .iter()
+        .map(|(name, stats)| {
+            let relative = if stats.mean == fastest {
+                "fastest".to_string()
+            } else {
+                format!(
+                    "{:.2}x slower",
+                    stats.mean.as_secs_f64() / fastest.max(MIN_DURATION).as_secs_f64()
+                )
+            };
+            [
+                (*name).to_string(),
+                format!("{:.1?} @ {} samples", stats.mean, stats.samples),
+                format!("median {:.1?}", stats.median),
+                relative,
+            ]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..4)
+        .map(|i| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    cells
+        .iter()
+        .map(|row| {
+            let padded: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect();
+            padded.join("  ").trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Appends the record to the records file, if one was passed with `--records`.
+fn write_record(record: &PartRecord, options: &RunOptions) {
+    if let Some(path) = &options.records_path {
+        if let Err(e) = protocol::write_record(path, record) {
+            eprintln!("Failed to write record to \"{}\": {e}", path.display());
+        }
+    }
+}
+
+/// Runs the function once, profiling its heap usage if `--memory` was passed.
+fn run_once<'a, I: ?Sized, T>(
+    func: &impl Fn(&'a I) -> T,
+    input: &'a I,
+    options: &RunOptions,
+) -> (T, Duration, Option<MemoryStats>) {
+    let timer = Instant::now();
+    let (result, memory) = profile_heap(options.is_memory, || func(input));
+    (result, timer.elapsed(), memory)
+}
+
+/// The statistics of a function that took `base_time` to run once. If `is_timed`,
+/// the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+fn collect_stats<'a, I: ?Sized, T>(
+    func: &impl Fn(&'a I) -> T,
+    input: &'a I,
+    base_time: &Duration,
+    is_timed: bool,
+) -> BenchStats {
+    if is_timed {
+        bench(func, input, base_time)
     } else {
-        (base_time, 1)
+        BenchStats::from_samples(&[*base_time])
+    }
+}
+
+/// Runs `func` under the dhat heap profiler.
+/// With `is_memory`, the heap statistics are returned instead of writing a `dhat-heap.json` report.
+#[cfg(feature = "dhat-heap")]
+fn profile_heap<T>(is_memory: bool, func: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    if !is_memory {
+        let _profiler = dhat::Profiler::new_heap();
+        return (func(), None);
+    }
+
+    // a testing profiler does not write a report when it is dropped.
+    let _profiler = dhat::Profiler::builder().testing().build();
+    let result = func();
+    let stats = dhat::HeapStats::get();
+
+    let memory = MemoryStats {
+        peak_bytes: stats.max_bytes as u64,
+        total_bytes: stats.total_bytes,
+        total_blocks: stats.total_blocks,
     };
 
-    (result, run.0, run.1)
+    (result, Some(memory))
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+#[cfg(not(feature = "dhat-heap"))]
+fn profile_heap<T>(is_memory: bool, func: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    if is_memory {
+        eprintln!("Measuring memory requires the `dhat-heap` feature.");
+    }
+    (func(), None)
+}
+
+fn bench<'a, I: ?Sized, T>(
+    func: &impl Fn(&'a I) -> T,
+    input: &'a I,
+    base_time: &Duration,
+) -> BenchStats {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let config = &config::get().bench;
+    let bench_iterations = (config.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(config.min_samples, config.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
         let timer = Instant::now();
-        black_box(func(black_box(cloned)));
+        black_box(func(black_box(input)));
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_stats(stats: &BenchStats, memory: Option<&MemoryStats>) -> String {
+    let memory = memory.map_or_else(String::new, |memory| {
+        format!(
+            " [peak {}, total {} in {} blocks]",
+            format_bytes(memory.peak_bytes),
+            format_bytes(memory.total_bytes),
+            memory.total_blocks
+        )
+    });
+
+    format!("{}{memory}", format_durations(stats))
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_durations(stats: &BenchStats) -> String {
+    let BenchStats {
+        samples,
+        mean,
+        min,
+        median,
+        p95,
+        std_dev,
+        outliers,
+    } = stats;
+
+    if *samples == 1 {
+        format!(" ({mean:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({mean:.1?} @ {samples} samples) [min {min:.1?}, median {median:.1?}, p95 {p95:.1?}, σ {std_dev:.1?}, {outliers} outliers]"
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Result<Option<T>, String>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(Some(result)) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -118,7 +606,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -126,44 +614,268 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(e) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ {ANSI_BOLD}failed{ANSI_RESET}: {e}{duration_str}");
+            }
+        }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Try to submit one part of the solution if:
+///  1. `--submit` was passed for this part.
+///  2. aoc-cli is installed, or the `client` feature is enabled.
+///  3. the answer was not already rejected, see [`Submissions::check`].
+///
+/// Every submission is logged with its verdict. Correct answers are added to the answer store.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+    options: &RunOptions,
+) -> Result<(), Error> {
+    if options.submit_part != Some(part) {
+        return Ok(());
+    }
+
+    let policy = &config::get().submit;
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+    if !policy.enabled {
+        return Err(Error::Refused("Submitting is disabled in aoc.toml.".into()));
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let puzzle = PuzzleId::new(Year::current(), day);
+    let answer = result.to_string();
+
+    let mut submissions = Submissions::read_from_file(puzzle)
+        .map_err(|e| Error::Parse(format!("Failed to read submissions: {e}")))?;
+
+    if let Err(refusal) = submissions.check(part, &answer) {
+        if policy.refuse_known_wrong {
+            return Err(Error::Refused(format!(
+                "Refusing to submit \"{answer}\": {refusal}"
+            )));
+        }
+        eprintln!("Warning: submitting \"{answer}\" anyway, {refusal}");
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    if submissions::is_suspicious_answer(&answer) {
+        if policy.refuse_suspicious {
+            return Err(Error::Refused(format!(
+                "Refusing to submit \"{answer}\", did the solution run at all?"
+            )));
+        }
+        eprintln!("Warning: submitting \"{answer}\", did the solution run at all?");
+    }
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+    // only log submissions that reached the server.
+    let response = send_answer(puzzle, part, &answer)?;
 
-    if part_submit != part {
-        return None;
+    let verdict = Verdict::parse(&response);
+
+    submissions
+        .data
+        .push(Submission::new(part, &answer, verdict));
+    if let Err(e) = submissions.store_file(puzzle) {
+        eprintln!("Failed to store submission: {e}");
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if verdict == Verdict::Correct {
+        match answers::store_answer(puzzle, part, &answer) {
+            Ok(()) => println!("Stored answer of part {part}."),
+            Err(e) => eprintln!("Failed to store answer: {e}"),
+        }
     }
 
+    Ok(())
+}
+
+/// Submits an answer via aoc-cli and returns its output, if the server was reached.
+#[cfg(not(feature = "client"))]
+fn send_answer(puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, Error> {
+    use crate::template::aoc_cli;
+
+    aoc_cli::check()?;
+
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+
+    match aoc_cli::submit(puzzle, part, answer) {
+        Ok(output) | Err(aoc_cli::AocCommandError::BadExitStatus(output)) => {
+            Ok(String::from_utf8_lossy(&output.stdout).into())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Submits an answer and returns the response, if the server was reached.
+#[cfg(feature = "client")]
+fn send_answer(puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, Error> {
+    println!("Submitting result...");
+
+    Ok(crate::template::aoc_client::submit(puzzle, part, answer)?)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_variants, BenchStats, InputSource, PartResult, RunOptions};
+    use crate::template::Error;
+    use std::{path::PathBuf, time::Duration};
+
+    fn parse_options(args: &[&str]) -> RunOptions {
+        let args: Vec<String> = args.iter().map(|x| x.to_string()).collect();
+        RunOptions::parse(&args).unwrap()
+    }
+
+    #[test]
+    fn parses_run_options() {
+        assert_eq!(parse_options(&["target/debug/01"]), RunOptions::default());
+        assert_eq!(
+            parse_options(&[
+                "01",
+                "--records",
+                "/tmp/01.jsonl",
+                "--time",
+                "--submit",
+                "2"
+            ]),
+            RunOptions {
+                is_timed: true,
+                is_memory: false,
+                submit_part: Some(2),
+                records_path: Some(PathBuf::from("/tmp/01.jsonl")),
+                input: InputSource::Puzzle,
+                is_variants: false,
+                only_part: None,
+            }
+        );
+        assert!(parse_options(&["01", "--variants"]).is_variants);
+        assert_eq!(parse_options(&["01", "--part", "2"]).only_part, Some(2));
+        assert!(!parse_options(&["01", "--part", "2"]).runs_part(1));
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(
+            parse_options(&["01", "--example"]).input,
+            InputSource::Example
+        );
+        assert_eq!(
+            parse_options(&["01", "--input", "-"]).input,
+            InputSource::Stdin
+        );
+        assert_eq!(
+            parse_options(&["01", "--input", "edge.txt"]).input,
+            InputSource::File(PathBuf::from("edge.txt"))
+        );
+        assert_eq!(
+            parse_options(&["01", "--example-file", "01-2.txt"]).input,
+            InputSource::ExampleFile(PathBuf::from("01-2.txt"))
+        );
+        assert!(InputSource::from_flags(Some("-".into()), None, true).is_err());
+        assert!(InputSource::from_flags(None, Some("01-2.txt".into()), true).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_run_options() {
+        for args in [
+            &["01", "--submit", "x"][..],
+            &["01", "--part"],
+            &["01", "--example", "--input", "-"],
+        ] {
+            let args: Vec<String> = args.iter().map(|x| x.to_string()).collect();
+            assert!(matches!(RunOptions::parse(&args), Err(Error::Usage(_))));
+        }
+    }
+
+    #[test]
+    fn converts_part_results() {
+        assert_eq!(Some(1).into_answer(), Ok(Some(1)));
+        assert_eq!(None::<u32>.into_answer(), Ok(None));
+        assert_eq!(Ok::<_, String>(1).into_answer(), Ok(Some(1)));
+        assert_eq!(
+            "x".parse::<u32>().into_answer(),
+            Err("invalid digit found in string".into())
+        );
+    }
+
+    #[test]
+    fn roundtrips_input_source_args() {
+        for input in [
+            InputSource::Puzzle,
+            InputSource::Example,
+            InputSource::Stdin,
+            InputSource::File(PathBuf::from("data/edge.txt")),
+        ] {
+            let mut args = vec!["01".to_string()];
+            args.extend(input.to_args());
+            assert_eq!(RunOptions::parse(&args).unwrap().input, input);
+        }
+    }
+
+    fn from_nanos(samples: &[u64]) -> BenchStats {
+        let samples: Vec<Duration> = samples.iter().copied().map(Duration::from_nanos).collect();
+        BenchStats::from_samples(&samples)
+    }
+
+    #[test]
+    fn computes_stats_of_single_sample() {
+        let stats = from_nanos(&[100]);
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.mean, Duration::from_nanos(100));
+        assert_eq!(stats.min, Duration::from_nanos(100));
+        assert_eq!(stats.median, Duration::from_nanos(100));
+        assert_eq!(stats.p95, Duration::from_nanos(100));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn computes_stats_of_unsorted_samples() {
+        let stats = from_nanos(&[40, 10, 30, 20]);
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.mean, Duration::from_nanos(25));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(25));
+        assert_eq!(stats.p95, Duration::from_nanos(39));
+        assert_eq!(stats.std_dev, Duration::from_nanos(13));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn formats_variants_relative_to_fastest() {
+        let rows = [
+            ("naive", from_nanos(&[4000, 4000])),
+            ("fast", from_nanos(&[1000])),
+        ];
+        assert_eq!(
+            format_variants(&rows),
+            vec![
+                "naive  4.0µs @ 2 samples  median 4.0µs  4.00x slower",
+                "fast   1.0µs @ 1 samples  median 1.0µs  fastest",
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_variants_that_take_no_time() {
+        let rows = [("naive", from_nanos(&[5])), ("fast", from_nanos(&[0]))];
+        assert_eq!(
+            format_variants(&rows),
+            vec![
+                "naive  5.0ns @ 1 samples  median 5.0ns  5.00x slower",
+                "fast   0.0ns @ 1 samples  median 0.0ns  fastest",
+            ]
+        );
+    }
+
+    #[test]
+    fn counts_outliers() {
+        let stats = from_nanos(&[10, 11, 10, 12, 11, 10, 500]);
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(11));
+        assert_eq!(stats.outliers, 1);
+    }
 }
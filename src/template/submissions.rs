@@ -0,0 +1,379 @@
+/// Log of all answers submitted for a day, e.g. `data/2024/submissions/01.json`.
+/// Used to refuse answers that are already known to be wrong before sending them.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    path::Path,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::PuzzleId;
+
+fn get_submissions_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path("submissions", "json")
+}
+
+/// The response of Advent of Code to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently.
+    RateLimited,
+    /// The part was already solved or is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    /// Parses the verdict from the output of `aoc submit`.
+    pub fn parse(output: &str) -> Self {
+        if output.contains("That's the right answer") {
+            Verdict::Correct
+        } else if output.contains("That's not the right answer") {
+            if output.contains("too high") {
+                Verdict::TooHigh
+            } else if output.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            }
+        } else if output.contains("You gave an answer too recently") {
+            Verdict::RateLimited
+        } else if output.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether the answer is known to be wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::RateLimited => "rate_limited",
+            Verdict::WrongLevel => "wrong_level",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Verdict::Correct,
+            Verdict::Incorrect,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::RateLimited,
+            Verdict::WrongLevel,
+            Verdict::Unknown,
+        ]
+        .into_iter()
+        .find(|v| v.as_str() == s)
+        .ok_or_else(|| format!("unknown verdict `{s}`."))
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    /// Seconds since the unix epoch.
+    pub submitted_at: u64,
+    pub verdict: Verdict,
+}
+
+impl Submission {
+    pub fn new(part: u8, answer: &str, verdict: Verdict) -> Self {
+        let submitted_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Self {
+            part,
+            answer: answer.into(),
+            submitted_at,
+            verdict,
+        }
+    }
+}
+
+/// The reason an answer should not be submitted.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    KnownWrong(Verdict),
+    /// The answer is at or above an answer that was too high.
+    AboveBound(String),
+    /// The answer is at or below an answer that was too low.
+    BelowBound(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::KnownWrong(verdict) => {
+                write!(f, "this answer was already submitted ({verdict}).")
+            }
+            Refusal::AboveBound(bound) => write!(f, "{bound} was already too high."),
+            Refusal::BelowBound(bound) => write!(f, "{bound} was already too low."),
+        }
+    }
+}
+
+/// All submissions of a single day.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Rehydrate the submissions of `puzzle`. If not present, returns an empty log.
+    pub fn read_from_file(puzzle: PuzzleId) -> Result<Self, String> {
+        match fs::read_to_string(get_submissions_path(puzzle)) {
+            Ok(s) => Submissions::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Dehydrate the submissions of `puzzle`.
+    pub fn store_file(&self, puzzle: PuzzleId) -> io::Result<()> {
+        let path = get_submissions_path(puzzle);
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
+        JsonValue::from(self).format_to(&mut file)
+    }
+
+    /// Checks an answer against earlier submissions of the same part.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Refusal> {
+        let submissions = self.data.iter().filter(|s| s.part == part);
+
+        for submission in submissions {
+            if submission.answer == answer && submission.verdict.is_wrong() {
+                return Err(Refusal::KnownWrong(submission.verdict));
+            }
+
+            let (Ok(value), Ok(bound)) =
+                (answer.parse::<i128>(), submission.answer.parse::<i128>())
+            else {
+                continue;
+            };
+
+            match submission.verdict {
+                Verdict::TooHigh if value >= bound => {
+                    return Err(Refusal::AboveBound(submission.answer.clone()));
+                }
+                Verdict::TooLow if value <= bound => {
+                    return Err(Refusal::BelowBound(submission.answer.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether an answer looks like the part did not do anything.
+pub fn is_suspicious_answer(answer: &str) -> bool {
+    let answer = answer.trim();
+    answer.is_empty() || answer == "0"
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submissions> for JsonValue {
+    fn from(value: &Submissions) -> Self {
+        let data = value
+            .data
+            .iter()
+            .map(|s| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("part".into(), JsonValue::Number(f64::from(s.part)));
+                map.insert("answer".into(), JsonValue::String(s.answer.clone()));
+                #[allow(clippy::cast_precision_loss)]
+                map.insert(
+                    "submitted_at".into(),
+                    JsonValue::Number(s.submitted_at as f64),
+                );
+                map.insert("verdict".into(), JsonValue::String(s.verdict.to_string()));
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("data".into(), JsonValue::Array(data));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).map_err(|x| x.to_string())?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected submissions to be a JSON object.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected submissions.data to be an array.")?;
+
+        data.iter()
+            .map(Submission::try_from)
+            .collect::<Result<_, _>>()
+            .map(|data| Submissions { data })
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected submission to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .copied()
+                .ok_or(format!("expected submission.{key} to be a number."))
+        };
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .ok_or(format!("expected submission.{key} to be a string."))
+        };
+
+        Ok(Submission {
+            part: number("part")? as u8,
+            answer: string("answer")?.clone(),
+            submitted_at: number("submitted_at")? as u64,
+            verdict: string("verdict")?.parse()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_suspicious_answer, Refusal, Submission, Submissions, Verdict};
+    use tinyjson::JsonValue;
+
+    fn submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                Submission::new(1, "100", Verdict::TooHigh),
+                Submission::new(1, "10", Verdict::TooLow),
+                Submission::new(1, "50", Verdict::Incorrect),
+                Submission::new(2, "abc", Verdict::Incorrect),
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        let parse = Verdict::parse;
+        assert_eq!(
+            parse("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(parse("That's not the right answer."), Verdict::Incorrect);
+        assert_eq!(
+            parse("You gave an answer too recently; you have to wait after submitting."),
+            Verdict::RateLimited
+        );
+        assert_eq!(
+            parse("You don't seem to be solving the right level."),
+            Verdict::WrongLevel
+        );
+        assert_eq!(parse(""), Verdict::Unknown);
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let submissions = submissions();
+        assert_eq!(
+            submissions.check(1, "50"),
+            Err(Refusal::KnownWrong(Verdict::Incorrect))
+        );
+        assert_eq!(
+            submissions.check(2, "abc"),
+            Err(Refusal::KnownWrong(Verdict::Incorrect))
+        );
+        assert_eq!(submissions.check(2, "50"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let submissions = submissions();
+        assert_eq!(
+            submissions.check(1, "100"),
+            Err(Refusal::KnownWrong(Verdict::TooHigh))
+        );
+        assert_eq!(
+            submissions.check(1, "120"),
+            Err(Refusal::AboveBound("100".into()))
+        );
+        assert_eq!(
+            submissions.check(1, "-3"),
+            Err(Refusal::BelowBound("10".into()))
+        );
+        assert_eq!(submissions.check(1, "42"), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submissions = submissions();
+        let s = JsonValue::from(&submissions).stringify().unwrap();
+        assert_eq!(Submissions::try_from(s).unwrap(), submissions);
+    }
+
+    #[test]
+    fn detects_suspicious_answers() {
+        assert!(is_suspicious_answer("0"));
+        assert!(is_suspicious_answer(" "));
+        assert!(!is_suspicious_answer("10"));
+    }
+}
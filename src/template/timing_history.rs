@@ -0,0 +1,313 @@
+/// History of all timings stored with `cargo time --store`, e.g. `data/2024/timings-history.json`.
+/// `timings.json` only keeps the latest timing of every day, this keeps every stored run.
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::{PartTiming, Timing, Timings};
+use crate::template::{data_dir, Day, Year};
+
+fn get_history_path(year: Year) -> String {
+    format!("./{}/timings-history.json", data_dir(year))
+}
+
+/// Characters of a sparkline, from the lowest to the highest value.
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// A single stored run of `cargo time`.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Short hash of the checked out commit, suffixed with `-dirty` if there were local changes.
+    /// Not present outside of a git repository.
+    pub commit: Option<String>,
+    /// Seconds since the unix epoch.
+    pub stored_at: u64,
+    /// Label of the machine that ran the benchmarks, see [`machine_label`].
+    pub machine: String,
+    /// Build profile of the benched solutions, e.g. `release`.
+    pub profile: String,
+    /// Timings of the days that were benched in this run.
+    pub timings: Vec<Timing>,
+}
+
+impl HistoryEntry {
+    /// An entry for `timings` that were just benched on this machine.
+    pub fn new(timings: &Timings, profile: &str) -> Self {
+        let stored_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Self {
+            commit: current_commit(),
+            stored_at,
+            machine: machine_label(),
+            profile: profile.into(),
+            timings: timings.data.clone(),
+        }
+    }
+
+    /// The date the entry was stored at, e.g. `2024-12-01`.
+    pub fn date(&self) -> String {
+        format_date(self.stored_at)
+    }
+
+    /// The timing of a part, if it was benched in this run.
+    pub fn part(&self, day: Day, part: u8) -> Option<&PartTiming> {
+        let timing = self.timings.iter().find(|t| t.day == day)?;
+        match part {
+            1 => timing.part_1.as_ref(),
+            2 => timing.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// All stored runs of a year, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct TimingHistory {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl TimingHistory {
+    /// Rehydrate the history of `year`. If not present, returns an empty history.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        match fs::read_to_string(get_history_path(year)) {
+            Ok(s) => TimingHistory::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(TimingHistory::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Dehydrate the history of `year`.
+    pub fn store_file(&self, year: Year) -> io::Result<()> {
+        fs::create_dir_all(data_dir(year))?;
+        let mut file = fs::File::create(get_history_path(year))?;
+        JsonValue::from(self).format_to(&mut file)
+    }
+
+    /// Entries that benched `day`, oldest first.
+    pub fn for_day(&self, day: Day) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.timings.iter().any(|t| t.day == day))
+    }
+}
+
+/// Label of the current machine. Set `AOC_MACHINE` to override the host name.
+pub fn machine_label() -> String {
+    env::var("AOC_MACHINE")
+        .ok()
+        .or_else(|| command_output("hostname", &[]))
+        .unwrap_or_else(|| "unknown".into())
+}
+
+fn current_commit() -> Option<String> {
+    let hash = command_output("git", &["rev-parse", "--short", "HEAD"])?;
+    let is_dirty = command_output("git", &["status", "--porcelain"]).is_some();
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+/// Trimmed stdout of a successful command, if not empty.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !stdout.is_empty()).then_some(stdout)
+}
+
+/// Renders values as a line of bars that are scaled between the minimum and the maximum.
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    values
+        .iter()
+        .map(|value| {
+            if max - min <= f64::EPSILON {
+                return SPARKS[SPARKS.len() / 2];
+            }
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let index = ((value - min) / (max - min) * (SPARKS.len() - 1) as f64).round() as usize;
+            SPARKS[index]
+        })
+        .collect()
+}
+
+/// Formats seconds since the unix epoch as a UTC date.
+/// See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+#[allow(clippy::cast_possible_wrap)]
+fn format_date(secs: u64) -> String {
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&TimingHistory> for JsonValue {
+    fn from(value: &TimingHistory) -> Self {
+        let entries = value
+            .entries
+            .iter()
+            .map(|entry| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                if let Some(commit) = &entry.commit {
+                    map.insert("commit".into(), JsonValue::String(commit.clone()));
+                }
+                #[allow(clippy::cast_precision_loss)]
+                map.insert(
+                    "stored_at".into(),
+                    JsonValue::Number(entry.stored_at as f64),
+                );
+                map.insert("machine".into(), JsonValue::String(entry.machine.clone()));
+                map.insert("profile".into(), JsonValue::String(entry.profile.clone()));
+                map.insert(
+                    "data".into(),
+                    JsonValue::Array(entry.timings.iter().map(JsonValue::from).collect()),
+                );
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("entries".into(), JsonValue::Array(entries));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for TimingHistory {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).map_err(|x| x.to_string())?;
+        let entries = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected timing history to be a JSON object.")?
+            .get("entries")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected timing history.entries to be an array.")?;
+
+        entries
+            .iter()
+            .map(HistoryEntry::try_from)
+            .collect::<Result<_, _>>()
+            .map(|entries| TimingHistory { entries })
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected history entry to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .ok_or(format!("expected history entry.{key} to be a string."))
+        };
+
+        let stored_at = json
+            .get("stored_at")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("expected history entry.stored_at to be a number.")?;
+
+        let timings = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected history entry.data to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(HistoryEntry {
+            commit: string("commit").ok().cloned(),
+            stored_at: *stored_at as u64,
+            machine: string("machine")?.clone(),
+            profile: string("profile")?.clone(),
+            timings,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_date, sparkline, HistoryEntry, TimingHistory};
+    use crate::day;
+    use crate::template::timings::{PartTiming, Timing};
+    use tinyjson::JsonValue;
+
+    fn entry(commit: Option<&str>, part_1_nanos: f64) -> HistoryEntry {
+        HistoryEntry {
+            commit: commit.map(Into::into),
+            stored_at: 1_733_011_200,
+            machine: "laptop".into(),
+            profile: "release".into(),
+            timings: vec![Timing {
+                day: day!(1),
+                parse: None,
+                part_1: Some(PartTiming::new(part_1_nanos)),
+                part_2: None,
+                total_nanos: part_1_nanos,
+                interruption: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn roundtrips_history() {
+        let history = TimingHistory {
+            entries: vec![entry(Some("a1b2c3d"), 2e6), entry(None, 1e6)],
+        };
+        let s = JsonValue::from(&history).stringify().unwrap();
+        let parsed = TimingHistory::try_from(s).unwrap();
+
+        assert_eq!(parsed.entries.len(), 2);
+        assert_eq!(parsed.entries[0].commit.as_deref(), Some("a1b2c3d"));
+        assert_eq!(parsed.entries[1].commit, None);
+        assert_eq!(parsed.entries[1].machine, "laptop");
+        assert_eq!(
+            parsed.entries[1].part(day!(1), 1),
+            Some(&PartTiming::new(1e6))
+        );
+        assert_eq!(parsed.entries[1].part(day!(1), 2), None);
+        assert_eq!(parsed.for_day(day!(1)).count(), 2);
+        assert_eq!(parsed.for_day(day!(2)).count(), 0);
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(1_733_011_200), "2024-12-01");
+        assert_eq!(format_date(1_709_208_000), "2024-02-29");
+    }
+
+    #[test]
+    fn draws_sparklines() {
+        assert_eq!(sparkline(&[1.0, 8.0, 4.5, 1.0]), "▁█▅▁");
+        assert_eq!(sparkline(&[3.0, 3.0]), "▅▅");
+        assert_eq!(sparkline(&[]), "");
+    }
+}
@@ -1,17 +1,168 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{data_dir, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+/// Parts faster than the resolution of the clock take `0ns`. They count as `1ns` when
+/// comparing, so that ratios stay finite.
+const MIN_NANOS: f64 = 1_f64;
+
+fn get_timings_path(year: Year) -> String {
+    format!("./{}/timings.json", data_dir(year))
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Only present for days with a parse function, see `solution!`.
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
+    /// Set if the day did not run to completion.
+    pub interruption: Option<Interruption>,
+}
+
+/// Why a day stopped before all of its parts finished.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interruption {
+    /// The day or one of its parts exceeded its timeout.
+    TimedOut,
+    /// The day was killed by a signal, e.g. after exceeding its CPU or memory limit.
+    Killed,
+}
+
+impl Interruption {
+    fn as_str(self) -> &'static str {
+        match self {
+            Interruption::TimedOut => "timed_out",
+            Interruption::Killed => "killed",
+        }
+    }
+}
+
+impl Display for Interruption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Interruption::TimedOut => f.write_str("timed out"),
+            Interruption::Killed => f.write_str("killed"),
+        }
+    }
+}
+
+impl FromStr for Interruption {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Interruption::TimedOut, Interruption::Killed]
+            .into_iter()
+            .find(|i| i.as_str() == s)
+            .ok_or_else(|| format!("unknown interruption `{s}`."))
+    }
+}
+
+/// Represents the benchmark result of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    pub mean_nanos: f64,
+    /// Not present for timings that were stored before statistics were recorded.
+    pub stats: Option<SampleStats>,
+    /// Only present if the part was profiled with `cargo time --memory`.
+    pub memory: Option<MemoryStats>,
+}
+
+/// Statistics over all samples of a benchmark run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SampleStats {
+    pub samples: u64,
+    pub min_nanos: f64,
+    pub median_nanos: f64,
+    pub p95_nanos: f64,
+    pub std_dev_nanos: f64,
+    pub outliers: u64,
+}
+
+/// Heap usage of a single run of a part, as profiled by dhat.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryStats {
+    /// Maximum number of bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+    pub total_bytes: u64,
+    pub total_blocks: u64,
+}
+
+impl PartTiming {
+    pub fn new(mean_nanos: f64) -> Self {
+        Self {
+            mean_nanos,
+            stats: None,
+            memory: None,
+        }
+    }
+
+    /// The mean duration, formatted like the runner prints it.
+    pub fn mean(&self) -> String {
+        format_nanos(self.mean_nanos)
+    }
+
+    /// Whether `self` is a noisy run that should not replace the `stored` timing: its samples
+    /// spread more than the stored ones, and its median is slower than the stored spread allows.
+    /// Runs without statistics are never considered noisy.
+    pub fn is_noisier_than(&self, stored: &PartTiming) -> bool {
+        let (Some(new), Some(old)) = (&self.stats, &stored.stats) else {
+            return false;
+        };
+
+        let relative_spread =
+            |stats: &SampleStats| stats.std_dev_nanos / stats.median_nanos.max(MIN_NANOS);
+        relative_spread(new) > relative_spread(old)
+            && new.median_nanos > old.median_nanos + old.std_dev_nanos
+    }
+}
+
+/// Formats nanoseconds the same way the runner formats a [`Duration`].
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos.round() as u64))
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+
+    for next in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+
+    format!("{value:.1} {unit}")
+}
+
+/// Parses a duration formatted by [`Duration`]'s debug output (e.g. `1.2ms`) into nanoseconds.
+pub fn parse_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,16 +173,17 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings to the JSON file of `year`.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        fs::create_dir_all(data_dir(year))?;
+        let mut file = fs::File::create(get_timings_path(year))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate timings from the JSON file of `year`. If not present, returns empty timings.
+    pub fn read_from_file(year: Year) -> Self {
+        let s = fs::read_to_string(get_timings_path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -44,12 +196,42 @@ impl Timings {
         }
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Merge two sets of timings, overwriting `self` with `new` if present.
+    /// Parts keep their stored timing if the new run is noisier and slower, see
+    /// [`PartTiming::is_noisier_than`]. Interrupted days keep the stored timings of the parts
+    /// they did not finish.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+
+            if let Some(stored) = self.data.iter().find(|t| t.day == timing.day) {
+                let parts = [
+                    (&mut timing.parse, &stored.parse),
+                    (&mut timing.part_1, &stored.part_1),
+                    (&mut timing.part_2, &stored.part_2),
+                ];
+
+                for (part, stored_part) in parts {
+                    let Some(stored_part) = stored_part else {
+                        continue;
+                    };
+
+                    let keep_stored = match part.as_ref() {
+                        Some(new_part) => new_part.is_noisier_than(stored_part),
+                        None => timing.interruption.is_some(),
+                    };
+
+                    if keep_stored {
+                        let new_nanos = part.as_ref().map_or(0_f64, |p| p.mean_nanos);
+                        timing.total_nanos += stored_part.mean_nanos - new_nanos;
+                        *part = Some(stored_part.clone());
+                    }
+                }
+            }
+
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -58,7 +240,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -67,10 +249,89 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Whether both parts of `day` have timings and the day was not interrupted, i.e. the
+    /// parts were not kept from an earlier run.
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+        self.data.iter().any(|t| {
+            t.day == day && t.part_1.is_some() && t.part_2.is_some() && t.interruption.is_none()
+        })
+    }
+
+    /// Compare `self` part by part against the `baseline` timings.
+    /// Days that are missing from `self` (i.e. were not run) are skipped.
+    pub fn compare(&self, baseline: &Self) -> Vec<PartComparison> {
+        let mut comparisons = vec![];
+
+        for old in &baseline.data {
+            let Some(new) = self.data.iter().find(|t| t.day == old.day) else {
+                continue;
+            };
+
+            let parts = [(1, &old.part_1, &new.part_1), (2, &old.part_2, &new.part_2)];
+
+            for (part, old_part, new_part) in parts {
+                let Some(old_part) = old_part else {
+                    continue;
+                };
+
+                let (baseline_nanos, current_nanos) = match new_part {
+                    Some(new_part) => {
+                        let (old_nanos, new_nanos) = comparable_nanos(old_part, new_part);
+                        (old_nanos, Some(new_nanos))
+                    }
+                    None => (old_part.mean_nanos, None),
+                };
+
+                comparisons.push(PartComparison {
+                    day: old.day,
+                    part,
+                    baseline_nanos,
+                    current_nanos,
+                });
+            }
+        }
+
+        comparisons
+    }
+}
+
+/// Picks the values to compare two part timings by:
+/// the median if both have statistics (it's robust against outliers), the mean otherwise.
+fn comparable_nanos(baseline: &PartTiming, current: &PartTiming) -> (f64, f64) {
+    match (&baseline.stats, &current.stats) {
+        (Some(old), Some(new)) => (old.median_nanos, new.median_nanos),
+        _ => (baseline.mean_nanos, current.mean_nanos),
+    }
+}
+
+/// Result of comparing a part's timing against its stored baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartComparison {
+    pub day: Day,
+    pub part: u8,
+    pub baseline_nanos: f64,
+    /// `None` if the part did not produce a timing anymore.
+    pub current_nanos: Option<f64>,
+}
+
+impl PartComparison {
+    /// Relative change against the baseline in percent. Positive values are slowdowns.
+    pub fn change_percent(&self) -> Option<f64> {
+        let baseline = self.baseline_nanos.max(MIN_NANOS);
+        self.current_nanos
+            .map(|current| (current.max(MIN_NANOS) - baseline) / baseline * 100_f64)
+    }
+
+    /// The current timing divided by the baseline. Values above `1` are slowdowns.
+    pub fn ratio(&self) -> Option<f64> {
+        self.current_nanos
+            .map(|current| current.max(MIN_NANOS) / self.baseline_nanos.max(MIN_NANOS))
+    }
+
+    /// A part regressed if it got slower by more than `threshold_percent` or is not solved anymore.
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.change_percent()
+            .is_none_or(|change| change > threshold_percent)
     }
 }
 
@@ -121,8 +382,12 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::from(parse));
+        }
+
+        let part_1 = value.part_1.as_ref().map(JsonValue::from);
+        let part_2 = value.part_2.as_ref().map(JsonValue::from);
 
         map.insert(
             "part_1".into(),
@@ -140,6 +405,13 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if let Some(interruption) = value.interruption {
+            map.insert(
+                "interruption".into(),
+                JsonValue::String(interruption.as_str().into()),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -158,26 +430,149 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        let parse = match json.get("parse") {
+            None => None,
+            Some(v) => parse_part(v).map_err(|e| format!("timing.parse: {e}"))?,
+        };
+
         let part_1 = json
             .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+            .ok_or("Expected timing.part_1 to be present.")
+            .and_then(parse_part)
+            .map_err(|e| format!("timing.part_1: {e}"))?;
 
         let part_2 = json
             .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+            .ok_or("Expected timing.part_2 to be present.")
+            .and_then(parse_part)
+            .map_err(|e| format!("timing.part_2: {e}"))?;
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let interruption = match json.get("interruption") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.interruption to be a string.")?
+                    .parse::<Interruption>()
+                    .map_err(|e| format!("timing.interruption: {e}"))?,
+            ),
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse,
+            part_1,
+            part_2,
             total_nanos,
+            interruption,
+        })
+    }
+}
+
+/// Parses a part timing that is either null, a legacy duration string or a timing object.
+fn parse_part(value: &JsonValue) -> Result<Option<PartTiming>, &'static str> {
+    if value.is_null() {
+        Ok(None)
+    } else if let Some(s) = value.get::<String>() {
+        parse_nanos(s)
+            .map(|nanos| Some(PartTiming::new(nanos)))
+            .ok_or("Expected duration string to be parseable.")
+    } else {
+        PartTiming::try_from(value).map(Some)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+
+        if let Some(stats) = &value.stats {
+            #[allow(clippy::cast_precision_loss)]
+            let numbers = [
+                ("samples", stats.samples as f64),
+                ("min_nanos", stats.min_nanos),
+                ("median_nanos", stats.median_nanos),
+                ("p95_nanos", stats.p95_nanos),
+                ("std_dev_nanos", stats.std_dev_nanos),
+                ("outliers", stats.outliers as f64),
+            ];
+
+            for (key, number) in numbers {
+                map.insert(key.into(), JsonValue::Number(number));
+            }
+        }
+
+        if let Some(memory) = &value.memory {
+            #[allow(clippy::cast_precision_loss)]
+            let numbers = [
+                ("peak_bytes", memory.peak_bytes as f64),
+                ("total_bytes", memory.total_bytes as f64),
+                ("total_blocks", memory.total_blocks as f64),
+            ];
+
+            for (key, number) in numbers {
+                map.insert(key.into(), JsonValue::Number(number));
+            }
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = &'static str;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be null, a string or an object.")?;
+
+        let number = |key: &str| json.get(key).and_then(|v| v.get::<f64>().copied());
+
+        let mean_nanos = number("mean_nanos").ok_or("Expected mean_nanos to be a number.")?;
+
+        // statistics are all-or-nothing.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let stats = match number("samples") {
+            None => None,
+            Some(samples) => Some(SampleStats {
+                samples: samples as u64,
+                min_nanos: number("min_nanos").ok_or("Expected min_nanos to be a number.")?,
+                median_nanos: number("median_nanos")
+                    .ok_or("Expected median_nanos to be a number.")?,
+                p95_nanos: number("p95_nanos").ok_or("Expected p95_nanos to be a number.")?,
+                std_dev_nanos: number("std_dev_nanos")
+                    .ok_or("Expected std_dev_nanos to be a number.")?,
+                outliers: number("outliers").ok_or("Expected outliers to be a number.")? as u64,
+            }),
+        };
+
+        // so are memory statistics.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let memory = match number("peak_bytes") {
+            None => None,
+            Some(peak_bytes) => Some(MemoryStats {
+                peak_bytes: peak_bytes as u64,
+                total_bytes: number("total_bytes").ok_or("Expected total_bytes to be a number.")?
+                    as u64,
+                total_blocks: number("total_blocks")
+                    .ok_or("Expected total_blocks to be a number.")?
+                    as u64,
+            }),
+        };
+
+        Ok(PartTiming {
+            mean_nanos,
+            stats,
+            memory,
         })
     }
 }
@@ -188,35 +583,44 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1: Some(PartTiming::new(10e6)),
+                    part_2: Some(PartTiming::new(20e6)),
                     total_nanos: 3e+10,
+                    interruption: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(PartTiming::new(30e6)),
+                    part_2: Some(PartTiming::new(40e6)),
                     total_nanos: 7e+10,
+                    interruption: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(PartTiming::new(40e6)),
                     part_2: None,
                     total_nanos: 4e+10,
+                    interruption: None,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{MemoryStats, PartTiming, SampleStats, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -225,11 +629,64 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1, Some(PartTiming::new(1_000_000_f64)));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_timing_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "mean_nanos": 1500, "samples": 10, "min_nanos": 1000, "median_nanos": 1400, "p95_nanos": 2000, "std_dev_nanos": 50, "outliers": 1 }, "part_2": { "mean_nanos": 20 }, "total_nanos": 1520 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.part_1,
+                Some(PartTiming {
+                    mean_nanos: 1500_f64,
+                    stats: Some(SampleStats {
+                        samples: 10,
+                        min_nanos: 1000_f64,
+                        median_nanos: 1400_f64,
+                        p95_nanos: 2000_f64,
+                        std_dev_nanos: 50_f64,
+                        outliers: 1,
+                    }),
+                    memory: None,
+                })
+            );
+            assert_eq!(timing.part_2, Some(PartTiming::new(20_f64)));
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_partial_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "mean_nanos": 1500, "samples": 10 }, "part_2": null, "total_nanos": 1500 }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
+        #[test]
+        fn handles_memory_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "mean_nanos": 1500, "peak_bytes": 1024, "total_bytes": 4096, "total_blocks": 8 }, "part_2": null, "total_nanos": 1500 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let part_1 = timings.data[0].part_1.as_ref().unwrap();
+            assert_eq!(
+                part_1.memory,
+                Some(MemoryStats {
+                    peak_bytes: 1024,
+                    total_bytes: 4096,
+                    total_blocks: 8,
+                })
+            );
+            assert_eq!(part_1.stats, None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_partial_memory_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "mean_nanos": 1500, "peak_bytes": 1024 }, "part_2": null, "total_nanos": 1500 }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -254,9 +711,89 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::{
+            day,
+            template::timings::{
+                Interruption, MemoryStats, PartTiming, SampleStats, Timing, Timings,
+            },
+        };
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn roundtrips_timing_stats() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    parse: None,
+                    part_1: Some(PartTiming {
+                        mean_nanos: 1500_f64,
+                        stats: Some(SampleStats {
+                            samples: 10,
+                            min_nanos: 1000_f64,
+                            median_nanos: 1400_f64,
+                            p95_nanos: 2000_f64,
+                            std_dev_nanos: 50_f64,
+                            outliers: 1,
+                        }),
+                        memory: Some(MemoryStats {
+                            peak_bytes: 2048,
+                            total_bytes: 4096,
+                            total_blocks: 12,
+                        }),
+                    }),
+                    part_2: None,
+                    total_nanos: 1500_f64,
+                    interruption: None,
+                }],
+            };
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(parsed.data[0].part_2, None);
+        }
+
+        #[test]
+        fn roundtrips_interruptions() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(14),
+                    parse: None,
+                    part_1: Some(PartTiming::new(1500_f64)),
+                    part_2: None,
+                    total_nanos: 1500_f64,
+                    interruption: Some(Interruption::TimedOut),
+                }],
+            };
+            let json = JsonValue::from(timings).stringify().unwrap();
+            assert!(json.contains(r#""interruption":"timed_out""#));
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].interruption, Some(Interruption::TimedOut));
+
+            let json = JsonValue::from(get_mock_timings()).stringify().unwrap();
+            assert!(!json.contains("interruption"));
+        }
+
+        #[test]
+        fn roundtrips_parse_timings() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(12),
+                    parse: Some(PartTiming::new(500_f64)),
+                    part_1: Some(PartTiming::new(1500_f64)),
+                    part_2: None,
+                    total_nanos: 2000_f64,
+                    interruption: None,
+                }],
+            };
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].parse, timings.data[0].parse);
+
+            let json = JsonValue::from(get_mock_timings()).stringify().unwrap();
+            assert!(!json.contains("parse"));
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -278,7 +815,7 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{PartTiming, Timing, Timings},
         };
 
         #[test]
@@ -286,13 +823,15 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    parse: None,
+                    part_1: Some(PartTiming::new(1e6)),
+                    part_2: Some(PartTiming::new(2e6)),
                     total_nanos: 3_000_000_000_f64,
+                    interruption: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -300,13 +839,15 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: Some(PartTiming::new(1e6)),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    interruption: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -314,33 +855,122 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    interruption: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{Interruption, PartTiming, SampleStats, Timing, Timings},
         };
 
         use super::get_mock_timings;
 
+        fn part(median_nanos: f64, std_dev_nanos: f64) -> PartTiming {
+            PartTiming {
+                mean_nanos: median_nanos,
+                stats: Some(SampleStats {
+                    samples: 100,
+                    min_nanos: median_nanos - std_dev_nanos,
+                    median_nanos,
+                    p95_nanos: median_nanos + 2_f64 * std_dev_nanos,
+                    std_dev_nanos,
+                    outliers: 0,
+                }),
+                memory: None,
+            }
+        }
+
+        fn timings(part_1: PartTiming) -> Timings {
+            Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    parse: None,
+                    total_nanos: part_1.mean_nanos,
+                    part_1: Some(part_1),
+                    part_2: None,
+                    interruption: None,
+                }],
+            }
+        }
+
+        #[test]
+        fn keeps_stored_timing_over_noisy_run() {
+            let stored = timings(part(100_f64, 5_f64));
+            let merged = stored.merge(&timings(part(150_f64, 60_f64)));
+            assert_eq!(merged.data[0].part_1, Some(part(100_f64, 5_f64)));
+            assert_eq!(merged.data[0].total_nanos, 100_f64);
+        }
+
+        #[test]
+        fn replaces_stored_timing_with_steady_run() {
+            let stored = timings(part(100_f64, 5_f64));
+
+            // slower, but just as steady: the solution changed.
+            let merged = stored.merge(&timings(part(150_f64, 6_f64)));
+            assert_eq!(merged.data[0].part_1, Some(part(150_f64, 6_f64)));
+            assert_eq!(merged.data[0].total_nanos, 150_f64);
+
+            // noisier, but faster.
+            let merged = stored.merge(&timings(part(80_f64, 30_f64)));
+            assert_eq!(merged.data[0].part_1, Some(part(80_f64, 30_f64)));
+        }
+
+        #[test]
+        fn compares_spread_of_parts_that_take_no_time() {
+            // a steady stored part of `0ns` is kept over a noisy, slower run.
+            let stored = timings(part(0_f64, 0_f64));
+            let merged = stored.merge(&timings(part(10_f64, 8_f64)));
+            assert_eq!(merged.data[0].part_1, Some(part(0_f64, 0_f64)));
+
+            // a stored part of `0ns` that spread is replaced by a steady run.
+            let stored = timings(part(0_f64, 2_f64));
+            let merged = stored.merge(&timings(part(10_f64, 1_f64)));
+            assert_eq!(merged.data[0].part_1, Some(part(10_f64, 1_f64)));
+        }
+
+        #[test]
+        fn keeps_stored_parts_of_interrupted_day() {
+            let mut stored = timings(part(100_f64, 5_f64));
+            stored.data[0].part_2 = Some(part(300_f64, 10_f64));
+            stored.data[0].total_nanos = 400_f64;
+
+            // part one finished, part two timed out.
+            let mut interrupted = timings(part(90_f64, 5_f64));
+            interrupted.data[0].interruption = Some(Interruption::TimedOut);
+
+            let merged = stored.merge(&interrupted);
+            assert_eq!(merged.data[0].part_1, Some(part(90_f64, 5_f64)));
+            assert_eq!(merged.data[0].part_2, Some(part(300_f64, 10_f64)));
+            assert_eq!(merged.data[0].total_nanos, 390_f64);
+            assert_eq!(merged.data[0].interruption, Some(Interruption::TimedOut));
+            assert!(!merged.is_day_complete(day!(1)));
+
+            // a complete run may drop a part, e.g. a day without a second part.
+            let merged = stored.merge(&timings(part(90_f64, 5_f64)));
+            assert_eq!(merged.data[0].part_2, None);
+        }
+
         #[test]
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    interruption: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -358,9 +988,11 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    interruption: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -388,4 +1020,115 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
     }
+
+    mod compare {
+        use crate::{
+            day,
+            template::timings::{PartComparison, PartTiming, SampleStats, Timing, Timings},
+        };
+
+        use super::get_mock_timings;
+
+        fn with_stats(mean_nanos: f64, median_nanos: f64) -> PartTiming {
+            PartTiming {
+                mean_nanos,
+                stats: Some(SampleStats {
+                    samples: 10,
+                    min_nanos: median_nanos,
+                    median_nanos,
+                    p95_nanos: median_nanos,
+                    std_dev_nanos: 0_f64,
+                    outliers: 0,
+                }),
+                memory: None,
+            }
+        }
+
+        #[test]
+        fn compares_parts_of_days_that_ran() {
+            let baseline = get_mock_timings();
+            let current = Timings {
+                data: vec![Timing {
+                    day: day!(2),
+                    parse: None,
+                    part_1: Some(PartTiming::new(15e6)),
+                    part_2: Some(PartTiming::new(50e6)),
+                    total_nanos: 65e6,
+                    interruption: None,
+                }],
+            };
+
+            let comparisons = current.compare(&baseline);
+            assert_eq!(comparisons.len(), 2);
+            assert_eq!(comparisons[0].day, day!(2));
+            assert_eq!(comparisons[0].part, 1);
+            assert_eq!(comparisons[0].change_percent(), Some(-50_f64));
+            assert_eq!(comparisons[0].is_regression(10_f64), false);
+            assert_eq!(comparisons[1].change_percent(), Some(25_f64));
+            assert_eq!(comparisons[1].is_regression(10_f64), true);
+            assert_eq!(comparisons[1].is_regression(30_f64), false);
+        }
+
+        #[test]
+        fn compares_parts_that_take_no_time() {
+            let comparison = |baseline_nanos, current_nanos| PartComparison {
+                day: day!(1),
+                part: 1,
+                baseline_nanos,
+                current_nanos: Some(current_nanos),
+            };
+
+            assert_eq!(comparison(0_f64, 0_f64).change_percent(), Some(0_f64));
+            assert_eq!(comparison(0_f64, 3_f64).change_percent(), Some(200_f64));
+            assert_eq!(comparison(4_f64, 0_f64).ratio(), Some(0.25));
+        }
+
+        #[test]
+        fn flags_parts_that_are_not_solved_anymore() {
+            let baseline = get_mock_timings();
+            let current = Timings {
+                data: vec![Timing {
+                    day: day!(4),
+                    parse: None,
+                    part_1: None,
+                    part_2: Some(PartTiming::new(1e6)),
+                    total_nanos: 1e6,
+                    interruption: None,
+                }],
+            };
+
+            let comparisons = current.compare(&baseline);
+            assert_eq!(comparisons.len(), 1);
+            assert_eq!(comparisons[0].current_nanos, None);
+            assert_eq!(comparisons[0].is_regression(100_f64), true);
+        }
+
+        #[test]
+        fn prefers_median_if_both_have_stats() {
+            let baseline = Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    parse: None,
+                    part_1: Some(with_stats(10e6, 8e6)),
+                    part_2: Some(with_stats(10e6, 8e6)),
+                    total_nanos: 20e6,
+                    interruption: None,
+                }],
+            };
+            let current = Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    parse: None,
+                    part_1: Some(with_stats(20e6, 8e6)),
+                    part_2: Some(PartTiming::new(12e6)),
+                    total_nanos: 32e6,
+                    interruption: None,
+                }],
+            };
+
+            let comparisons = current.compare(&baseline);
+            assert_eq!(comparisons[0].change_percent(), Some(0_f64));
+            assert_eq!(comparisons[1].change_percent(), Some(20_f64));
+        }
+    }
 }
@@ -0,0 +1,179 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::config::{self, Config};
+use crate::template::{Day, MAX_DAY};
+
+/// The year used when neither `AOC_YEAR` nor `aoc.toml` set one.
+const FALLBACK_YEAR: u16 = 2024;
+
+/// The first event with fewer puzzle days.
+const FIRST_SHORT_EVENT: u16 = 2025;
+/// The number of puzzle days of events since [`FIRST_SHORT_EVENT`].
+const SHORT_EVENT_DAYS: u8 = 12;
+
+/// A valid year of advent of code (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a year with an event,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < 2015 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Reads the year from the `AOC_YEAR` environment variable, if set.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// Returns the year that commands default to when no `--year` is passed.
+    ///
+    /// This is `AOC_YEAR` from the environment, falling back to the `year` in `aoc.toml`.
+    pub fn current() -> Self {
+        Self::from_env()
+            .or(config::get().year)
+            .unwrap_or(Self(FALLBACK_YEAR))
+    }
+
+    /// The last puzzle day of the event, set in `aoc.toml` or defaulting to 25 until 2024
+    /// and 12 since 2025.
+    pub fn last_day(self) -> u8 {
+        self.last_day_in(config::get())
+    }
+
+    /// The last puzzle day of the event according to `config`, see [`Year::last_day`].
+    pub fn last_day_in(self, config: &Config) -> u8 {
+        config
+            .days
+            .get(&self)
+            .copied()
+            .unwrap_or(if self.0 < FIRST_SHORT_EVENT {
+                MAX_DAY
+            } else {
+                SHORT_EVENT_DAYS
+            })
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies a single puzzle by its year and day.
+///
+/// # Display
+/// This value displays as `<year>/<day>`.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2023).unwrap(), Day::new(5).unwrap());
+/// assert_eq!(puzzle.to_string(), "2023/05")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Path of a data file for this puzzle, e.g. `data/2024/inputs/01.txt`.
+    pub fn data_path(&self, folder: &str, extension: &str) -> String {
+        format!("{}/{folder}/{}.{extension}", data_dir(self.year), self.day)
+    }
+
+    /// Name of the solution binary, e.g. `2024_01`.
+    pub fn bin_name(&self) -> String {
+        format!("{}_{}", self.year, self.day)
+    }
+
+    /// Path of the solution module, e.g. `src/bin/2024_01.rs`.
+    pub fn bin_path(&self) -> String {
+        format!("src/bin/{}.rs", self.bin_name())
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/// The directory that holds all data (inputs, examples, puzzles, timings) of a year.
+pub fn data_dir(year: Year) -> String {
+    format!("{}/{year}", config::get().data_dir)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PuzzleId, Year};
+    use crate::day;
+
+    #[test]
+    fn rejects_years_before_first_event() {
+        assert_eq!(Year::new(2014), None);
+        assert_eq!(Year::new(2015), Some(Year(2015)));
+        assert!("1999".parse::<Year>().is_err());
+        assert!("abc".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn builds_year_aware_paths() {
+        let puzzle = PuzzleId::new(Year(2023), day!(7));
+        assert_eq!(puzzle.data_path("inputs", "txt"), "data/2023/inputs/07.txt");
+        assert_eq!(puzzle.data_path("puzzles", "md"), "data/2023/puzzles/07.md");
+        assert_eq!(puzzle.bin_name(), "2023_07");
+        assert_eq!(puzzle.bin_path(), "src/bin/2023_07.rs");
+    }
+}